@@ -0,0 +1,75 @@
+const { execSync } = require('child_process')
+const fs = require('fs')
+const path = require('path')
+const pkg = require('./package.json')
+
+const icon = path.join(__dirname, 'assets', 'icon')
+
+// Shipped next to app.asar when `npm run build:sidecar` has been run.
+const sidecar = path.join(__dirname, 'target', 'release', process.platform === 'win32' ? 'zaphnath-sidecar.exe' : 'zaphnath-sidecar')
+
+// CI can pin these; local builds fall back to the checked-out commit.
+const version = process.env.ZAPHNATH_VERSION || pkg.version
+const commit = process.env.ZAPHNATH_COMMIT || (() => {
+  try {
+    return execSync('git rev-parse --short HEAD', { cwd: __dirname, stdio: ['ignore', 'pipe', 'ignore'] }).toString().trim()
+  } catch {
+    return 'unknown'
+  }
+})()
+
+const linuxOptions = {
+  name: 'zaphnath',
+  productName: pkg.productName,
+  genericName: 'Zaphnath',
+  icon: `${icon}.png`,
+  categories: ['Utility'],
+  mimeType: ['x-scheme-handler/zaphnath'],
+  homepage: 'https://github.com/beabzk/zaphnath',
+  version,
+}
+
+module.exports = {
+  packagerConfig: {
+    name: pkg.productName,
+    executableName: 'zaphnath',
+    appBundleId: 'com.beabzk.zaphnath',
+    appVersion: version,
+    buildVersion: `${version}+${commit}`,
+    icon,
+    protocols: [{ name: 'Zaphnath', schemes: ['zaphnath'] }],
+    extraResource: fs.existsSync(sidecar) ? [sidecar] : [],
+    // Native addons cannot be loaded from inside the archive.
+    asar: { unpack: '**/*.node' },
+    ignore: [
+      /^\/\.vscode($|\/)/,
+      /^\/\.gitignore$/,
+      /^\/out($|\/)/,
+      /^\/test($|\/)/,
+      /^\/playwright\.config\.js$/,
+      /^\/requests\.jsonl$/,
+      /^\/forge\.config\.js$/,
+      /^\/scripts($|\/)/,
+      /^\/target($|\/)/,
+      /^\/Cargo\.(toml|lock)$/,
+      /^\/(native|sidecar)\/(src|tests|build\.rs|Cargo\.toml)($|\/)/,
+    ],
+  },
+  hooks: {
+    // The packaged app reads this to report its version and commit.
+    packageAfterCopy: async (config, buildPath) => {
+      const info = { version, commit, builtAt: new Date().toISOString() }
+      fs.writeFileSync(path.join(buildPath, 'build-info.json'), `${JSON.stringify(info, null, 2)}\n`)
+      // app.getVersion() and the deb/rpm metadata read the copied package.json,
+      // so it has to carry an injected version too.
+      const manifest = path.join(buildPath, 'package.json')
+      fs.writeFileSync(manifest, `${JSON.stringify({ ...JSON.parse(fs.readFileSync(manifest, 'utf8')), version }, null, 2)}\n`)
+    },
+  },
+  makers: [
+    { name: '@electron-forge/maker-zip', platforms: ['linux', 'darwin', 'win32'] },
+    { name: '@electron-forge/maker-deb', config: { options: { ...linuxOptions, maintainer: pkg.author, section: 'utils' } } },
+    { name: '@electron-forge/maker-rpm', config: { options: { ...linuxOptions, license: pkg.license } } },
+    { name: '@reforged/maker-appimage', config: { options: { ...linuxOptions, name: pkg.productName } } },
+  ],
+}
@@ -1,27 +1,64 @@
 const { app, BrowserWindow } = require('electron')
-const { ipcMain } = require('electron')
-const path = require('path')
-
-const createWindow = () => {
-  const win = new BrowserWindow({
-    width: 800,
-    height: 600,
-    webPreferences: {
-        preload: path.join(__dirname, 'preload.js'),
-    },
+const crashes = require('./src/main/crashes')
+const csp = require('./src/main/csp')
+const deepLink = require('./src/main/deep-link')
+const { registerHandlers } = require('./src/main/handlers')
+const i18n = require('./src/main/i18n')
+const jobs = require('./src/main/jobs')
+const menu = require('./src/main/menu')
+const open = require('./src/main/open')
+const protocol = require('./src/main/protocol')
+const recovery = require('./src/main/recovery')
+const security = require('./src/main/security')
+const sidecar = require('./src/main/sidecar')
+const theme = require('./src/main/theme')
+const tray = require('./src/main/tray')
+const updater = require('./src/main/updater')
+const windows = require('./src/main/windows')
+
+// Lets tests and side-by-side profiles run against their own data directory.
+if (process.env.ZAPHNATH_USER_DATA) app.setPath('userData', process.env.ZAPHNATH_USER_DATA)
+
+const startPrimary = () => {
+  crashes.start()
+  recovery.start()
+  protocol.registerScheme()
+  deepLink.register()
+
+  app.on('second-instance', (event, argv, workingDirectory) => {
+    windows.focusOrOpen('main')
+    open.handleArgv(argv, workingDirectory)
   })
-  ipcMain.handle('ping', () => 'pong')
-  win.loadFile('index.html')
-}
 
-app.whenReady().then(() => {
-  createWindow()
+  app.whenReady().then(() => {
+    protocol.install()
+    security.install()
+    csp.install()
+    i18n.start()
+    registerHandlers()
+    theme.start()
+    menu.install()
+    updater.start()
+    sidecar.start()
+    tray.start()
+    windows.open('main')
+    open.handleArgv(process.argv)
+    open.start()
 
-  app.on('activate', () => {
-    if (BrowserWindow.getAllWindows().length === 0) createWindow()
+    app.on('activate', () => {
+      if (BrowserWindow.getAllWindows().length === 0) windows.open('main')
+    })
   })
-})
 
-app.on('window-all-closed', () => {
-    if (process.platform !== 'darwin') app.quit()
-  })
\ No newline at end of file
+  app.on('will-quit', () => {
+    jobs.stop()
+    sidecar.stop()
+  })
+
+  app.on('window-all-closed', () => {
+    if (process.platform !== 'darwin' && !tray.keepsAppRunning()) app.quit()
+  })
+}
+
+if (app.requestSingleInstanceLock()) startPrimary()
+else app.quit()
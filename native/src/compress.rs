@@ -0,0 +1,60 @@
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::io::{self, Read, Write};
+
+pub fn gzip(data: &[u8], level: u32) -> io::Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::new(level));
+    encoder.write_all(data)?;
+    encoder.finish()
+}
+
+/// Matches the largest payload the IPC layer accepts.
+pub const MAX_OUTPUT: u64 = 64 * 1024 * 1024;
+
+// Reads one byte past `limit` so output of exactly `limit` bytes still passes.
+pub fn gunzip(data: &[u8], limit: u64) -> io::Result<Vec<u8>> {
+    let mut output = Vec::new();
+    MultiGzDecoder::new(data)
+        .take(limit + 1)
+        .read_to_end(&mut output)?;
+    if output.len() as u64 > limit {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("decompressed data exceeds {limit} bytes"),
+        ));
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips() {
+        let data = "zaphnath ".repeat(1000);
+        let compressed = gzip(data.as_bytes(), 9).unwrap();
+        assert!(compressed.len() < data.len() / 10);
+        assert_eq!(gunzip(&compressed, MAX_OUTPUT).unwrap(), data.as_bytes());
+    }
+
+    #[test]
+    fn rejects_data_that_is_not_gzip() {
+        assert!(gunzip(b"not gzip", MAX_OUTPUT).is_err());
+    }
+
+    #[test]
+    fn decodes_every_member() {
+        let mut data = gzip(b"first ", 6).unwrap();
+        data.extend(gzip(b"second", 6).unwrap());
+        assert_eq!(gunzip(&data, MAX_OUTPUT).unwrap(), b"first second");
+    }
+
+    #[test]
+    fn stops_at_the_output_limit() {
+        let compressed = gzip(&[0; 1025], 9).unwrap();
+        assert!(gunzip(&compressed, 1024).is_err());
+        assert_eq!(gunzip(&compressed, 1025).unwrap().len(), 1025);
+    }
+}
@@ -0,0 +1,100 @@
+use crate::hash::to_hex;
+use sha2::{Digest, Sha256};
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+const CHUNK_SIZE: usize = 64 * 1024;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct FileStats {
+    pub bytes: u64,
+    /// A trailing newline does not start another line.
+    pub lines: u64,
+    pub longest_line: u64,
+    pub sha256: String,
+}
+
+pub fn inspect(path: impl AsRef<Path>) -> io::Result<FileStats> {
+    let mut file = File::open(path)?;
+    let mut buffer = vec![0; CHUNK_SIZE];
+    let mut hasher = Sha256::new();
+    let (mut bytes, mut lines, mut longest_line, mut current) = (0u64, 0u64, 0u64, 0u64);
+
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        let chunk = &buffer[..read];
+        hasher.update(chunk);
+        bytes += read as u64;
+        for &byte in chunk {
+            if byte == b'\n' {
+                lines += 1;
+                longest_line = longest_line.max(current);
+                current = 0;
+            } else {
+                current += 1;
+            }
+        }
+    }
+    if current > 0 {
+        lines += 1;
+        longest_line = longest_line.max(current);
+    }
+
+    Ok(FileStats {
+        bytes,
+        lines,
+        longest_line,
+        sha256: to_hex(&hasher.finalize()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hash::sha256_hex;
+
+    fn inspect_bytes(name: &str, contents: &[u8]) -> FileStats {
+        let path =
+            std::env::temp_dir().join(format!("zaphnath-inspect-{}-{name}", std::process::id()));
+        std::fs::write(&path, contents).unwrap();
+        let stats = inspect(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        stats
+    }
+
+    #[test]
+    fn counts_lines_with_and_without_a_trailing_newline() {
+        assert_eq!(inspect_bytes("trailing", b"one\nthree\n").lines, 2);
+        assert_eq!(inspect_bytes("bare", b"one\nthree").lines, 2);
+        assert_eq!(inspect_bytes("empty", b"").lines, 0);
+    }
+
+    #[test]
+    fn spans_chunk_boundaries() {
+        let mut contents = vec![b'x'; CHUNK_SIZE + 10];
+        contents.push(b'\n');
+        contents.extend_from_slice(b"tail");
+        let stats = inspect_bytes("chunks", &contents);
+        assert_eq!(
+            stats,
+            FileStats {
+                bytes: contents.len() as u64,
+                lines: 2,
+                longest_line: CHUNK_SIZE as u64 + 10,
+                sha256: sha256_hex(&contents),
+            }
+        );
+    }
+
+    #[test]
+    fn reports_missing_files() {
+        assert_eq!(
+            inspect("/nonexistent/zaphnath").unwrap_err().kind(),
+            io::ErrorKind::NotFound
+        );
+    }
+}
@@ -0,0 +1,125 @@
+//! Native helpers for the main process, loaded through `src/main/native.js`.
+//! Every export here has a pure-JS twin in that file; keep the two in step.
+
+#[macro_use]
+extern crate napi_derive;
+
+mod compress;
+mod hash;
+mod inspect;
+
+use napi::bindgen_prelude::{AsyncTask, Buffer};
+use napi::{Env, Error, Result, Task};
+
+fn to_napi(error: std::io::Error) -> Error {
+    Error::from_reason(error.to_string())
+}
+
+pub struct Sha256 {
+    data: Buffer,
+}
+
+impl Task for Sha256 {
+    type Output = String;
+    type JsValue = String;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        Ok(hash::sha256_hex(&self.data))
+    }
+
+    fn resolve(&mut self, _env: Env, hex: Self::Output) -> Result<Self::JsValue> {
+        Ok(hex)
+    }
+}
+
+/// Hex-encoded SHA-256 of `data`.
+#[napi(ts_return_type = "Promise<string>")]
+pub fn sha256(data: Buffer) -> AsyncTask<Sha256> {
+    AsyncTask::new(Sha256 { data })
+}
+
+pub struct Gzip {
+    data: Buffer,
+    level: u32,
+}
+
+impl Task for Gzip {
+    type Output = Vec<u8>;
+    type JsValue = Buffer;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        compress::gzip(&self.data, self.level).map_err(to_napi)
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(Buffer::from(output))
+    }
+}
+
+/// Gzip-compresses `data`. `level` runs from 0 (store) to 9 (smallest).
+#[napi(ts_return_type = "Promise<Buffer>")]
+pub fn gzip(data: Buffer, level: Option<u32>) -> AsyncTask<Gzip> {
+    let level = level.unwrap_or(6).min(9);
+    AsyncTask::new(Gzip { data, level })
+}
+
+pub struct Gunzip {
+    data: Buffer,
+}
+
+impl Task for Gunzip {
+    type Output = Vec<u8>;
+    type JsValue = Buffer;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        compress::gunzip(&self.data, compress::MAX_OUTPUT).map_err(to_napi)
+    }
+
+    fn resolve(&mut self, _env: Env, output: Self::Output) -> Result<Self::JsValue> {
+        Ok(Buffer::from(output))
+    }
+}
+
+/// Decompresses every gzip member in `data`, failing once the output would
+/// pass `compress::MAX_OUTPUT` bytes.
+#[napi(ts_return_type = "Promise<Buffer>")]
+pub fn gunzip(data: Buffer) -> AsyncTask<Gunzip> {
+    AsyncTask::new(Gunzip { data })
+}
+
+#[napi(object)]
+pub struct FileStats {
+    pub bytes: i64,
+    pub lines: i64,
+    pub longest_line: i64,
+    pub sha256: String,
+}
+
+pub struct InspectFile {
+    path: String,
+}
+
+impl Task for InspectFile {
+    type Output = inspect::FileStats;
+    type JsValue = FileStats;
+
+    fn compute(&mut self) -> Result<Self::Output> {
+        inspect::inspect(&self.path).map_err(to_napi)
+    }
+
+    fn resolve(&mut self, _env: Env, stats: Self::Output) -> Result<Self::JsValue> {
+        Ok(FileStats {
+            bytes: stats.bytes as i64,
+            lines: stats.lines as i64,
+            longest_line: stats.longest_line as i64,
+            sha256: stats.sha256,
+        })
+    }
+}
+
+/// Streams the file at `path` on the libuv thread pool, so large files never
+/// block the main process.
+#[napi(ts_return_type = "Promise<FileStats>")]
+pub fn inspect_file(path: String) -> AsyncTask<InspectFile> {
+    AsyncTask::new(InspectFile { path })
+}
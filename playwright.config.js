@@ -0,0 +1,11 @@
+const { defineConfig } = require('@playwright/test')
+
+module.exports = defineConfig({
+  testDir: './test/e2e',
+  timeout: 30 * 1000,
+  workers: 1,
+  reporter: process.env.CI ? 'line' : 'list',
+  use: {
+    trace: 'retain-on-failure',
+  },
+})
@@ -1,8 +1,51 @@
 const { contextBridge, ipcRenderer } = require('electron')
 
-contextBridge.exposeInMainWorld('versions', {
+const api = {
+  versions: {
     node: () => process.versions.node,
     chrome: () => process.versions.chrome,
     electron: () => process.versions.electron,
-    ping: () => ipcRenderer.invoke('ping'),
-})
\ No newline at end of file
+  },
+}
+
+const subscribe = (name) => (callback) => {
+  const listener = (event, payload) => callback(payload)
+  ipcRenderer.on(name, listener)
+  return () => ipcRenderer.removeListener(name, listener)
+}
+
+// The main process owns the channel registry; the sandboxed preload cannot
+// require it, so it asks for the list once and builds the bridge from that.
+for (const { name, kind, bridge: [namespace, method] } of ipcRenderer.sendSync('ipc:manifest')) {
+  api[namespace] = api[namespace] || {}
+  api[namespace][method] = kind === 'event'
+    ? subscribe(name)
+    : kind === 'send'
+      ? (...args) => ipcRenderer.send(name, ...args)
+      : (...args) => ipcRenderer.invoke(name, ...args)
+}
+
+// Errors do not survive the bridge intact, so flatten them before sending.
+const plain = (fields) => fields && Object.fromEntries(Object.entries(fields).map(([key, value]) => [
+  key,
+  value instanceof Error ? { name: value.name, message: value.message, stack: value.stack } : value,
+]))
+
+for (const level of ['debug', 'info', 'warn', 'error']) {
+  api.log[level] = (message, fields) => ipcRenderer.send('log:write', level, String(message).slice(0, 4096), plain(fields))
+}
+
+document.addEventListener('securitypolicyviolation', (event) => {
+  ipcRenderer.send('csp:violation', {
+    directive: event.effectiveDirective,
+    blockedUri: event.blockedURI,
+    documentUri: event.documentURI,
+    sourceFile: event.sourceFile,
+    lineNumber: event.lineNumber,
+    sample: event.sample,
+  })
+})
+
+for (const [namespace, methods] of Object.entries(api)) {
+  contextBridge.exposeInMainWorld(namespace, methods)
+}
@@ -0,0 +1,21 @@
+// Builds the optional Rust addon in native/ and copies it to where
+// src/main/native.js looks for it. Needs a Rust toolchain; without one the
+// app keeps using the JavaScript fallbacks.
+const { execFileSync } = require('child_process')
+const fs = require('fs')
+const path = require('path')
+
+const root = path.join(__dirname, '..')
+const profile = process.argv.includes('--debug') ? 'debug' : 'release'
+
+const libraryName = {
+  win32: 'zaphnath_native.dll',
+  darwin: 'libzaphnath_native.dylib',
+}[process.platform] || 'libzaphnath_native.so'
+
+execFileSync('cargo', ['build', '-p', 'zaphnath-native', ...(profile === 'release' ? ['--release'] : [])], { cwd: root, stdio: 'inherit' })
+
+const target = process.env.CARGO_TARGET_DIR || path.join(root, 'target')
+const output = path.join(root, 'native', 'zaphnath.node')
+fs.copyFileSync(path.join(target, profile, libraryName), output)
+console.log(`Wrote ${path.relative(root, output)}`)
@@ -0,0 +1,127 @@
+//! Zaphnath's sidecar: a long-running backend the main process spawns and
+//! supervises (see src/main/sidecar.js). It reads JSON-RPC 2.0 requests from
+//! stdin, runs each on its own thread (at most `MAX_IN_FLIGHT` at once) and
+//! writes responses to stdout. Logs
+//! go to stderr. Closing stdin cancels outstanding work and exits.
+
+mod methods;
+mod rpc;
+
+use rpc::{Error, Request, Response, CANCEL_METHOD};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Requests beyond this are turned away rather than given another thread.
+const MAX_IN_FLIGHT: usize = 16;
+
+type InFlight = Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>;
+
+fn cancel(in_flight: &InFlight, params: &Value) {
+    let Some(id) = params.get("id") else { return };
+    if let Some(flag) = in_flight.lock().unwrap().get(&id.to_string()) {
+        flag.store(true, Ordering::Relaxed);
+    }
+}
+
+fn dispatch(request: Request, in_flight: &InFlight, responses: &Sender<Response>) {
+    if request.method == CANCEL_METHOD {
+        return cancel(in_flight, &request.params);
+    }
+    // Other notifications have nothing to answer and nothing we act on.
+    let Request {
+        id, method, params, ..
+    } = request;
+    let Some(id) = id else { return };
+
+    let key = id.to_string();
+    let flag = Arc::new(AtomicBool::new(false));
+    {
+        let mut in_flight = in_flight.lock().unwrap();
+        if in_flight.contains_key(&key) {
+            let error = Error::new(
+                rpc::INVALID_REQUEST,
+                format!("Request {key} is already in flight"),
+            );
+            let _ = responses.send(Response::new(id, Err(error)));
+            return;
+        }
+        if in_flight.len() >= MAX_IN_FLIGHT {
+            let error = Error::new(
+                rpc::SERVER_BUSY,
+                format!("Sidecar is busy: {MAX_IN_FLIGHT} requests already in flight"),
+            );
+            let _ = responses.send(Response::new(id, Err(error)));
+            return;
+        }
+        in_flight.insert(key.clone(), flag.clone());
+    }
+
+    let in_flight = in_flight.clone();
+    let responses = responses.clone();
+    thread::spawn(move || {
+        let outcome =
+            panic::catch_unwind(AssertUnwindSafe(|| methods::call(&method, params, &flag)))
+                .unwrap_or_else(|_| {
+                    Err(Error::new(
+                        rpc::INTERNAL_ERROR,
+                        format!("Method '{method}' panicked"),
+                    ))
+                });
+        in_flight.lock().unwrap().remove(&key);
+        // A request cancelled mid-flight reports cancellation even if it
+        // managed to finish.
+        let outcome = if flag.load(Ordering::Relaxed) {
+            Err(Error::cancelled())
+        } else {
+            outcome
+        };
+        let _ = responses.send(Response::new(id, outcome));
+    });
+}
+
+fn main() {
+    let (responses, outgoing) = mpsc::channel::<Response>();
+    let writer = thread::spawn(move || {
+        let mut stdout = io::stdout().lock();
+        for response in outgoing {
+            let written = serde_json::to_writer(&mut stdout, &response)
+                .map_err(io::Error::from)
+                .and_then(|()| stdout.write_all(b"\n"))
+                .and_then(|()| stdout.flush());
+            if written.is_err() {
+                break;
+            }
+        }
+    });
+
+    eprintln!(
+        "{} {} ready",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION")
+    );
+    let in_flight: InFlight = Arc::default();
+    for line in io::stdin().lock().lines() {
+        let Ok(line) = line else { break };
+        if line.trim().is_empty() {
+            continue;
+        }
+        match rpc::parse(&line) {
+            Ok(request) => dispatch(request, &in_flight, &responses),
+            Err(response) => {
+                let _ = responses.send(*response);
+            }
+        }
+    }
+
+    for flag in in_flight.lock().unwrap().values() {
+        flag.store(true, Ordering::Relaxed);
+    }
+    drop(responses);
+    let _ = writer.join();
+}
@@ -0,0 +1,186 @@
+//! The methods the sidecar serves. Long-running ones poll `cancel` so a
+//! `$/cancelRequest` or a client-side timeout stops them promptly.
+
+use crate::rpc::Error;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const MAX_SLEEP_MS: u64 = 10 * 60 * 1000;
+const MAX_PRIME_LIMIT: u64 = 10_000_000_000;
+const SEGMENT_SIZE: u64 = 1 << 16;
+
+pub fn call(method: &str, params: Value, cancel: &AtomicBool) -> Result<Value, Error> {
+    match method {
+        "ping" => Ok(json!("pong")),
+        "info" => Ok(json!({
+            "name": env!("CARGO_PKG_NAME"),
+            "version": env!("CARGO_PKG_VERSION"),
+            "pid": std::process::id(),
+        })),
+        "sleep" => sleep(parse(params)?, cancel),
+        "countPrimes" => count_primes(parse(params)?, cancel),
+        _ => Err(Error::new(
+            crate::rpc::METHOD_NOT_FOUND,
+            format!("Unknown method '{method}'"),
+        )),
+    }
+}
+
+fn parse<T: DeserializeOwned>(params: Value) -> Result<T, Error> {
+    serde_json::from_value(params).map_err(|error| Error::invalid_params(error.to_string()))
+}
+
+fn check(cancel: &AtomicBool) -> Result<(), Error> {
+    if cancel.load(Ordering::Relaxed) {
+        Err(Error::cancelled())
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Deserialize)]
+struct SleepParams {
+    ms: u64,
+}
+
+fn sleep(SleepParams { ms }: SleepParams, cancel: &AtomicBool) -> Result<Value, Error> {
+    if ms > MAX_SLEEP_MS {
+        return Err(Error::invalid_params(format!(
+            "ms must be at most {MAX_SLEEP_MS}"
+        )));
+    }
+    let deadline = Instant::now() + Duration::from_millis(ms);
+    while let Some(left) = deadline.checked_duration_since(Instant::now()) {
+        check(cancel)?;
+        thread::sleep(left.min(Duration::from_millis(10)));
+    }
+    Ok(Value::Null)
+}
+
+#[derive(Deserialize)]
+struct CountPrimesParams {
+    limit: u64,
+}
+
+/// Counts primes up to and including `limit` with a segmented sieve, checking
+/// for cancellation between segments.
+fn count_primes(
+    CountPrimesParams { limit }: CountPrimesParams,
+    cancel: &AtomicBool,
+) -> Result<Value, Error> {
+    if limit > MAX_PRIME_LIMIT {
+        return Err(Error::invalid_params(format!(
+            "limit must be at most {MAX_PRIME_LIMIT}"
+        )));
+    }
+    if limit < 2 {
+        return Ok(json!(0));
+    }
+
+    let root = (limit as f64).sqrt() as u64 + 1;
+    let mut composite = vec![false; root as usize + 1];
+    let mut base = Vec::new();
+    for n in 2..=root {
+        if !composite[n as usize] {
+            base.push(n);
+            let mut multiple = n * n;
+            while multiple <= root {
+                composite[multiple as usize] = true;
+                multiple += n;
+            }
+        }
+    }
+
+    let mut count = 0u64;
+    let mut low = 2;
+    let mut segment = vec![true; SEGMENT_SIZE as usize];
+    while low <= limit {
+        check(cancel)?;
+        let high = (low + SEGMENT_SIZE - 1).min(limit);
+        segment.iter_mut().for_each(|flag| *flag = true);
+        for &prime in &base {
+            if prime * prime > high {
+                break;
+            }
+            let mut multiple = (prime * prime).max(low.div_ceil(prime) * prime);
+            while multiple <= high {
+                segment[(multiple - low) as usize] = false;
+                multiple += prime;
+            }
+        }
+        count += segment[..(high - low + 1) as usize]
+            .iter()
+            .filter(|&&prime| prime)
+            .count() as u64;
+        low = high + 1;
+    }
+    Ok(json!(count))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rpc::{INVALID_PARAMS, METHOD_NOT_FOUND, REQUEST_CANCELLED};
+
+    fn run(method: &str, params: Value) -> Result<Value, Error> {
+        call(method, params, &AtomicBool::new(false))
+    }
+
+    #[test]
+    fn counts_primes() {
+        for (limit, expected) in [
+            (0, 0),
+            (1, 0),
+            (2, 1),
+            (10, 4),
+            (100, 25),
+            (1_000_000, 78_498),
+        ] {
+            assert_eq!(
+                run("countPrimes", json!({ "limit": limit })),
+                Ok(json!(expected)),
+                "limit {limit}"
+            );
+        }
+    }
+
+    #[test]
+    fn stops_when_cancelled() {
+        let cancelled = AtomicBool::new(true);
+        assert_eq!(
+            call("countPrimes", json!({ "limit": 1000 }), &cancelled)
+                .unwrap_err()
+                .code,
+            REQUEST_CANCELLED
+        );
+        assert_eq!(
+            call("sleep", json!({ "ms": 60_000 }), &cancelled)
+                .unwrap_err()
+                .code,
+            REQUEST_CANCELLED
+        );
+    }
+
+    #[test]
+    fn validates_params() {
+        assert_eq!(
+            run("sleep", json!({ "ms": -1 })).unwrap_err().code,
+            INVALID_PARAMS
+        );
+        assert_eq!(
+            run("sleep", json!({ "ms": MAX_SLEEP_MS + 1 }))
+                .unwrap_err()
+                .code,
+            INVALID_PARAMS
+        );
+        assert_eq!(
+            run("countPrimes", Value::Null).unwrap_err().code,
+            INVALID_PARAMS
+        );
+        assert_eq!(run("nope", Value::Null).unwrap_err().code, METHOD_NOT_FOUND);
+    }
+}
@@ -0,0 +1,166 @@
+//! JSON-RPC 2.0 message types. Messages are framed as one JSON document per
+//! line on stdin and stdout.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+pub const INTERNAL_ERROR: i64 = -32603;
+/// Implementation-defined server error: too many requests are in flight.
+pub const SERVER_BUSY: i64 = -32000;
+/// Same code the Language Server Protocol uses for cancelled requests.
+pub const REQUEST_CANCELLED: i64 = -32800;
+
+/// Notification the client sends to cancel an in-flight request.
+pub const CANCEL_METHOD: &str = "$/cancelRequest";
+
+#[derive(Debug, Deserialize)]
+pub struct Request {
+    pub jsonrpc: String,
+    /// Absent for notifications, which never get a response.
+    #[serde(default)]
+    pub id: Option<Value>,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct Error {
+    pub code: i64,
+    pub message: String,
+}
+
+impl Error {
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Error {
+            code,
+            message: message.into(),
+        }
+    }
+
+    pub fn invalid_params(message: impl Into<String>) -> Self {
+        Error::new(INVALID_PARAMS, message)
+    }
+
+    pub fn cancelled() -> Self {
+        Error::new(REQUEST_CANCELLED, "Request cancelled")
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Response {
+    pub jsonrpc: &'static str,
+    pub id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<Error>,
+}
+
+impl Response {
+    pub fn new(id: Value, outcome: Result<Value, Error>) -> Self {
+        match outcome {
+            Ok(result) => Response {
+                jsonrpc: "2.0",
+                id,
+                result: Some(result),
+                error: None,
+            },
+            Err(error) => Response {
+                jsonrpc: "2.0",
+                id,
+                result: None,
+                error: Some(error),
+            },
+        }
+    }
+}
+
+/// Parses one line into a request, or into the error response owed to the
+/// client when the line is not a valid request.
+pub fn parse(line: &str) -> Result<Request, Box<Response>> {
+    let value: Value = serde_json::from_str(line).map_err(|error| {
+        Box::new(Response::new(
+            Value::Null,
+            Err(Error::new(PARSE_ERROR, error.to_string())),
+        ))
+    })?;
+    let id = value.get("id").cloned().unwrap_or(Value::Null);
+    let invalid = |message: String| {
+        Box::new(Response::new(
+            id.clone(),
+            Err(Error::new(INVALID_REQUEST, message)),
+        ))
+    };
+    let request: Request =
+        serde_json::from_value(value.clone()).map_err(|error| invalid(error.to_string()))?;
+    if request.jsonrpc != "2.0" {
+        return Err(invalid("jsonrpc must be \"2.0\"".into()));
+    }
+    if !matches!(
+        request.id,
+        None | Some(Value::Number(_)) | Some(Value::String(_))
+    ) {
+        return Err(invalid("id must be a number or a string".into()));
+    }
+    Ok(request)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn error_code(line: &str) -> i64 {
+        parse(line).unwrap_err().error.as_ref().unwrap().code
+    }
+
+    #[test]
+    fn parses_requests_and_notifications() {
+        let request = parse(r#"{"jsonrpc":"2.0","id":1,"method":"ping"}"#).unwrap();
+        assert_eq!(
+            (request.id, request.method.as_str(), request.params),
+            (Some(json!(1)), "ping", Value::Null)
+        );
+        assert_eq!(
+            parse(r#"{"jsonrpc":"2.0","method":"$/cancelRequest","params":{"id":1}}"#)
+                .unwrap()
+                .id,
+            None
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_messages() {
+        assert_eq!(error_code("{"), PARSE_ERROR);
+        assert_eq!(
+            error_code(r#"{"jsonrpc":"1.0","id":1,"method":"ping"}"#),
+            INVALID_REQUEST
+        );
+        assert_eq!(
+            error_code(r#"{"jsonrpc":"2.0","id":{},"method":"ping"}"#),
+            INVALID_REQUEST
+        );
+        assert_eq!(error_code(r#"{"jsonrpc":"2.0","id":7}"#), INVALID_REQUEST);
+        assert_eq!(
+            parse(r#"{"jsonrpc":"2.0","id":7}"#).unwrap_err().id,
+            json!(7)
+        );
+    }
+
+    #[test]
+    fn serializes_only_result_or_error() {
+        let ok = serde_json::to_value(Response::new(json!(1), Ok(json!("pong")))).unwrap();
+        assert_eq!(ok, json!({ "jsonrpc": "2.0", "id": 1, "result": "pong" }));
+        let failed =
+            serde_json::to_value(Response::new(json!("a"), Err(Error::cancelled()))).unwrap();
+        assert_eq!(
+            failed,
+            json!({ "jsonrpc": "2.0", "id": "a", "error": { "code": REQUEST_CANCELLED, "message": "Request cancelled" } })
+        );
+    }
+}
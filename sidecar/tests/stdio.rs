@@ -0,0 +1,104 @@
+use serde_json::{json, Value};
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+
+struct Sidecar {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: BufReader<ChildStdout>,
+}
+
+impl Sidecar {
+    fn spawn() -> Self {
+        let mut child = Command::new(env!("CARGO_BIN_EXE_zaphnath-sidecar"))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+        let stdin = child.stdin.take().unwrap();
+        let stdout = BufReader::new(child.stdout.take().unwrap());
+        Sidecar {
+            child,
+            stdin,
+            stdout,
+        }
+    }
+
+    fn send(&mut self, message: Value) {
+        writeln!(self.stdin, "{message}").unwrap();
+    }
+
+    fn receive(&mut self) -> Value {
+        let mut line = String::new();
+        self.stdout.read_line(&mut line).unwrap();
+        serde_json::from_str(&line).unwrap()
+    }
+}
+
+impl Drop for Sidecar {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[test]
+fn answers_requests() {
+    let mut sidecar = Sidecar::spawn();
+    sidecar.send(json!({ "jsonrpc": "2.0", "id": 1, "method": "ping" }));
+    assert_eq!(
+        sidecar.receive(),
+        json!({ "jsonrpc": "2.0", "id": 1, "result": "pong" })
+    );
+    sidecar.send(json!({ "jsonrpc": "2.0", "id": "primes", "method": "countPrimes", "params": { "limit": 100 } }));
+    assert_eq!(
+        sidecar.receive(),
+        json!({ "jsonrpc": "2.0", "id": "primes", "result": 25 })
+    );
+}
+
+#[test]
+fn cancels_in_flight_requests() {
+    let mut sidecar = Sidecar::spawn();
+    sidecar
+        .send(json!({ "jsonrpc": "2.0", "id": 1, "method": "sleep", "params": { "ms": 60_000 } }));
+    sidecar.send(json!({ "jsonrpc": "2.0", "id": 2, "method": "ping" }));
+    assert_eq!(sidecar.receive()["id"], json!(2));
+
+    sidecar.send(json!({ "jsonrpc": "2.0", "method": "$/cancelRequest", "params": { "id": 1 } }));
+    let response = sidecar.receive();
+    assert_eq!(response["id"], json!(1));
+    assert_eq!(response["error"]["code"], json!(-32800));
+}
+
+#[test]
+fn turns_requests_away_when_saturated() {
+    let mut sidecar = Sidecar::spawn();
+    for id in 0..16 {
+        sidecar.send(
+            json!({ "jsonrpc": "2.0", "id": id, "method": "sleep", "params": { "ms": 60_000 } }),
+        );
+    }
+    sidecar.send(json!({ "jsonrpc": "2.0", "id": "extra", "method": "ping" }));
+    let response = sidecar.receive();
+    assert_eq!(response["id"], json!("extra"));
+    assert_eq!(response["error"]["code"], json!(-32000));
+
+    sidecar.send(json!({ "jsonrpc": "2.0", "method": "$/cancelRequest", "params": { "id": 0 } }));
+    assert_eq!(sidecar.receive()["id"], json!(0));
+    sidecar.send(json!({ "jsonrpc": "2.0", "id": "again", "method": "ping" }));
+    assert_eq!(sidecar.receive()["result"], json!("pong"));
+}
+
+#[test]
+fn exits_when_stdin_closes() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_zaphnath-sidecar"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    drop(child.stdin.take());
+    assert!(child.wait().unwrap().success());
+}
@@ -0,0 +1,348 @@
+// Every IPC channel the renderer can reach is declared here. `bridge` is the
+// [namespace, method] the preload script exposes it as; `args` and `returns`
+// are checked in the main process on every call. Channels with
+// `kind: 'event'` flow from main to renderer and check `payload` instead;
+// `kind: 'send'` channels are fire-and-forget messages from the renderer.
+const jobTasks = require('./job-tasks')
+const views = require('./views')
+
+const themeState = {
+  type: 'object',
+  properties: {
+    mode: { enum: ['system', 'light', 'dark'] },
+    resolved: { enum: ['light', 'dark'] },
+  },
+}
+const catalog = {
+  type: 'object',
+  properties: {
+    locale: { type: 'string' },
+    dir: { enum: ['ltr', 'rtl'] },
+    messages: { type: 'object', additionalProperties: { oneOf: [{ type: 'string' }, { type: 'object', additionalProperties: { type: 'string' } }] } },
+    available: { type: 'array', items: { type: 'object', properties: { code: { type: 'string' }, name: { type: 'string' } } } },
+  },
+}
+const windowKind = { enum: ['main', 'settings', 'about', 'document'] }
+const settingKey = { type: 'string', maxLength: 128, optional: true }
+const updateStatus = {
+  type: 'object',
+  properties: {
+    state: { enum: ['idle', 'disabled', 'checking', 'up-to-date', 'downloading', 'ready', 'error'] },
+    version: { type: 'string', optional: true },
+    progress: { type: 'number', minimum: 0, maximum: 1, optional: true },
+    notes: { type: 'string', optional: true },
+    message: { type: 'string', optional: true },
+  },
+}
+const maxNativeInput = 64 * 1024 * 1024
+const sidecarStatus = {
+  type: 'object',
+  properties: {
+    state: { enum: ['stopped', 'starting', 'running', 'restarting', 'unavailable'] },
+    pid: { oneOf: [{ type: 'number' }, { type: 'null' }] },
+    restarts: { type: 'number' },
+  },
+}
+const job = {
+  type: 'object',
+  properties: {
+    id: { type: 'number' },
+    task: { type: 'string' },
+    state: { enum: ['queued', 'running', 'completed', 'failed', 'cancelled'] },
+    progress: { type: 'number', minimum: 0, maximum: 1 },
+    partial: { type: 'any' },
+    result: { type: 'any' },
+    error: { oneOf: [{ type: 'string' }, { type: 'null' }] },
+    createdAt: { type: 'string' },
+    startedAt: { oneOf: [{ type: 'string' }, { type: 'null' }] },
+    finishedAt: { oneOf: [{ type: 'string' }, { type: 'null' }] },
+  },
+}
+const nullableString = { oneOf: [{ type: 'string' }, { type: 'null' }] }
+const crashReport = {
+  type: 'object',
+  properties: {
+    id: { type: 'string' },
+    file: { type: 'string' },
+    createdAt: { type: 'string' },
+    process: { type: 'string' },
+    appVersion: { type: 'string' },
+    activeWindow: nullableString,
+    lastIpc: { type: 'array', items: { type: 'string' } },
+    uploadedAt: nullableString,
+    remoteId: nullableString,
+  },
+}
+
+module.exports = {
+  'app:navigate': {
+    kind: 'event',
+    bridge: ['app', 'onNavigate'],
+    payload: { type: 'object', properties: { view: { enum: views } } },
+  },
+  ping: {
+    bridge: ['versions', 'ping'],
+    args: [],
+    returns: { type: 'string' },
+  },
+  'commands:list': {
+    bridge: ['commands', 'list'],
+    args: [],
+    returns: {
+      type: 'array',
+      items: {
+        type: 'object',
+        properties: {
+          id: { type: 'string' },
+          label: { type: 'string' },
+          accelerator: { oneOf: [{ type: 'string' }, { type: 'null' }] },
+        },
+      },
+    },
+  },
+  'commands:execute': {
+    bridge: ['commands', 'execute'],
+    args: [{ type: 'string', maxLength: 64 }],
+    returns: { type: 'null' },
+  },
+  // `null` restores the default shortcut and '' removes it.
+  'commands:setAccelerator': {
+    bridge: ['commands', 'setAccelerator'],
+    args: [{ type: 'string', maxLength: 64 }, { oneOf: [{ type: 'string', maxLength: 64 }, { type: 'null' }] }],
+    returns: { oneOf: [{ type: 'string' }, { type: 'null' }] },
+  },
+  'commands:invoke': {
+    kind: 'event',
+    bridge: ['commands', 'onInvoke'],
+    payload: { type: 'object', properties: { id: { type: 'string' } } },
+  },
+  'crashes:list': {
+    bridge: ['crashes', 'list'],
+    args: [],
+    returns: { type: 'array', items: crashReport },
+  },
+  'crashes:upload': {
+    bridge: ['crashes', 'upload'],
+    args: [{ type: 'string', pattern: '^[\\w-]+$', maxLength: 128 }],
+    returns: crashReport,
+  },
+  'csp:violation': {
+    kind: 'send',
+    args: [{
+      type: 'object',
+      properties: {
+        directive: { type: 'string', maxLength: 256 },
+        blockedUri: { type: 'string', maxLength: 2048 },
+        documentUri: { type: 'string', maxLength: 2048 },
+        sourceFile: { type: 'string', maxLength: 2048 },
+        lineNumber: { type: 'number' },
+        sample: { type: 'string', maxLength: 256 },
+      },
+    }],
+  },
+  'diagnostics:snapshot': {
+    bridge: ['diagnostics', 'snapshot'],
+    args: [],
+    returns: {
+      type: 'object',
+      properties: {
+        generatedAt: { type: 'string' },
+        app: { type: 'object', additionalProperties: { type: 'any' } },
+        os: { type: 'object', additionalProperties: { type: 'any' } },
+        versions: { type: 'object', additionalProperties: { type: 'string' } },
+        gpu: { type: 'object', additionalProperties: { type: 'string' } },
+        metrics: { type: 'array', items: { type: 'object', additionalProperties: { type: 'any' } } },
+        paths: { type: 'object', additionalProperties: { type: 'string' } },
+        settings: { type: 'object', additionalProperties: { type: 'any' } },
+      },
+    },
+  },
+  'diagnostics:copyReport': {
+    bridge: ['diagnostics', 'copyReport'],
+    args: [],
+    returns: { type: 'null' },
+  },
+  'i18n:getCatalog': {
+    bridge: ['i18n', 'getCatalog'],
+    args: [],
+    returns: catalog,
+  },
+  'i18n:changed': {
+    kind: 'event',
+    bridge: ['i18n', 'onChange'],
+    payload: catalog,
+  },
+  'jobs:start': {
+    bridge: ['jobs', 'start'],
+    // The handler also checks params against the schema for the chosen task.
+    args: [{ enum: Object.keys(jobTasks) }, { oneOf: Object.values(jobTasks) }],
+    returns: job,
+  },
+  'jobs:cancel': {
+    bridge: ['jobs', 'cancel'],
+    args: [{ type: 'number', integer: true }],
+    returns: { type: 'boolean' },
+  },
+  'jobs:list': {
+    bridge: ['jobs', 'list'],
+    args: [],
+    returns: { type: 'array', items: job },
+  },
+  'jobs:update': {
+    kind: 'event',
+    bridge: ['jobs', 'onUpdate'],
+    payload: job,
+  },
+  'log:write': {
+    kind: 'send',
+    bridge: ['log', 'write'],
+    args: [
+      { enum: ['debug', 'info', 'warn', 'error'] },
+      { type: 'string', maxLength: 4096 },
+      { type: 'object', optional: true },
+    ],
+  },
+  'native:backend': {
+    bridge: ['native', 'backend'],
+    args: [],
+    returns: { enum: ['native', 'javascript'] },
+  },
+  'native:sha256': {
+    bridge: ['native', 'sha256'],
+    args: [{ type: 'bytes', maxLength: maxNativeInput }],
+    returns: { type: 'string' },
+  },
+  'native:gzip': {
+    bridge: ['native', 'gzip'],
+    args: [{ type: 'bytes', maxLength: maxNativeInput }, { type: 'number', integer: true, minimum: 0, maximum: 9, optional: true }],
+    returns: { type: 'bytes' },
+  },
+  'native:gunzip': {
+    bridge: ['native', 'gunzip'],
+    args: [{ type: 'bytes', maxLength: maxNativeInput }],
+    returns: { type: 'bytes' },
+  },
+  'native:inspectFile': {
+    bridge: ['native', 'inspectFile'],
+    args: [],
+    returns: {
+      oneOf: [{ type: 'null' }, {
+        type: 'object',
+        properties: {
+          name: { type: 'string' },
+          bytes: { type: 'number' },
+          lines: { type: 'number' },
+          longestLine: { type: 'number' },
+          sha256: { type: 'string' },
+        },
+      }],
+    },
+  },
+  'settings:get': {
+    bridge: ['settings', 'get'],
+    args: [settingKey],
+    returns: { type: 'any' },
+  },
+  'settings:set': {
+    bridge: ['settings', 'set'],
+    args: [{ type: 'string', maxLength: 128 }, { type: 'any' }],
+    returns: { type: 'any' },
+  },
+  'settings:changed': {
+    kind: 'event',
+    bridge: ['settings', 'onChange'],
+    payload: { type: 'object', properties: { key: { type: 'string' }, value: { type: 'any' } } },
+  },
+  'sidecar:call': {
+    bridge: ['sidecar', 'call'],
+    args: [
+      { enum: ['ping', 'info', 'sleep', 'countPrimes'] },
+      { type: 'any', optional: true },
+      {
+        type: 'object',
+        optional: true,
+        properties: {
+          timeout: { type: 'number', integer: true, minimum: 1, maximum: 10 * 60 * 1000, optional: true },
+          requestId: { type: 'string', maxLength: 64, optional: true },
+        },
+      },
+    ],
+    returns: { type: 'any' },
+  },
+  'sidecar:cancel': {
+    bridge: ['sidecar', 'cancel'],
+    args: [{ type: 'string', maxLength: 64 }],
+    returns: { type: 'boolean' },
+  },
+  'sidecar:getStatus': {
+    bridge: ['sidecar', 'getStatus'],
+    args: [],
+    returns: sidecarStatus,
+  },
+  'sidecar:status': {
+    kind: 'event',
+    bridge: ['sidecar', 'onStatus'],
+    payload: sidecarStatus,
+  },
+  'theme:get': {
+    bridge: ['theme', 'get'],
+    args: [],
+    returns: themeState,
+  },
+  'theme:changed': {
+    kind: 'event',
+    bridge: ['theme', 'onChange'],
+    payload: themeState,
+  },
+  'tray:setStatus': {
+    bridge: ['tray', 'setStatus'],
+    args: [{
+      type: 'object',
+      properties: {
+        tooltip: { type: 'string', maxLength: 100, optional: true },
+        badge: { type: 'number', integer: true, minimum: 0, maximum: 9999, optional: true },
+      },
+    }],
+    returns: { type: 'null' },
+  },
+  'updater:check': {
+    bridge: ['updater', 'check'],
+    args: [],
+    returns: updateStatus,
+  },
+  'updater:getStatus': {
+    bridge: ['updater', 'getStatus'],
+    args: [],
+    returns: updateStatus,
+  },
+  'updater:install': {
+    bridge: ['updater', 'install'],
+    args: [],
+    returns: { type: 'null' },
+  },
+  'updater:status': {
+    kind: 'event',
+    bridge: ['updater', 'onStatus'],
+    payload: updateStatus,
+  },
+  'windows:open': {
+    bridge: ['windows', 'open'],
+    args: [
+      windowKind,
+      { type: 'object', optional: true, additionalProperties: { type: 'string', maxLength: 1024 } },
+    ],
+    returns: { type: 'number', integer: true },
+  },
+  'windows:current': {
+    bridge: ['windows', 'current'],
+    args: [],
+    returns: {
+      type: 'object',
+      properties: {
+        id: { type: 'number', integer: true },
+        kind: windowKind,
+        params: { type: 'object', additionalProperties: { type: 'string' } },
+      },
+    },
+  },
+}
@@ -0,0 +1,134 @@
+const { app, BrowserWindow, shell } = require('electron')
+const { EventEmitter } = require('events')
+const diagnostics = require('./diagnostics')
+const i18n = require('./i18n')
+const ipc = require('./ipc')
+const logger = require('./logger')
+const settings = require('./settings')
+const updater = require('./updater')
+const windows = require('./windows')
+
+// Labels come from the `command.<id>` message. Commands with
+// `renderer: true` are handled by the focused window's renderer; everything
+// else runs here. `devOnly` commands are hidden in packaged builds.
+const definitions = [
+  {
+    id: 'window.new',
+    accelerator: 'CmdOrCtrl+N',
+    run: () => windows.open('document'),
+  },
+  {
+    id: 'view.commandPalette',
+    accelerator: 'CmdOrCtrl+Shift+P',
+    renderer: true,
+  },
+  {
+    id: 'view.reload',
+    accelerator: 'CmdOrCtrl+R',
+    run: ({ window }) => window && window.webContents.reload(),
+  },
+  {
+    id: 'view.toggleDevTools',
+    accelerator: process.platform === 'darwin' ? 'Alt+Cmd+I' : 'Ctrl+Shift+I',
+    devOnly: true,
+    run: ({ window }) => window && window.webContents.toggleDevTools(),
+  },
+  {
+    id: 'app.preferences',
+    accelerator: 'CmdOrCtrl+,',
+    run: () => windows.focusOrOpen('settings'),
+  },
+  {
+    id: 'app.about',
+    run: () => windows.focusOrOpen('about'),
+  },
+  {
+    id: 'app.checkForUpdates',
+    run: () => updater.check(),
+  },
+  {
+    id: 'help.openLogs',
+    run: () => shell.openPath(logger.directory()),
+  },
+  {
+    id: 'help.exportDiagnostics',
+    run: ({ window }) => diagnostics.exportBundle(window),
+  },
+  {
+    id: 'app.quit',
+    accelerator: 'CmdOrCtrl+Q',
+    run: () => app.quit(),
+  },
+]
+
+const MODIFIERS = ['Command', 'Cmd', 'Control', 'Ctrl', 'CommandOrControl', 'CmdOrCtrl', 'Alt', 'Option', 'AltGr', 'Shift', 'Super', 'Meta']
+const PUNCTUATION = ')!@#$%^&*(:;=<,_-.>?/~`{]}[|\\\'"'
+const NAMED_KEYS = [
+  'Plus', 'Space', 'Tab', 'Capslock', 'Numlock', 'Scrolllock', 'Backspace', 'Delete', 'Insert', 'Return', 'Enter',
+  'Up', 'Down', 'Left', 'Right', 'Home', 'End', 'PageUp', 'PageDown', 'Escape', 'Esc', 'PrintScreen',
+  'VolumeUp', 'VolumeDown', 'VolumeMute', 'MediaNextTrack', 'MediaPreviousTrack', 'MediaStop', 'MediaPlayPause',
+  'numdec', 'numadd', 'numsub', 'nummult', 'numdiv',
+]
+
+const isKey = (key) =>
+  /^([0-9A-Za-z]|F([1-9]|1[0-9]|2[0-4])|num[0-9])$/.test(key) || (key.length === 1 && PUNCTUATION.includes(key)) || NAMED_KEYS.includes(key)
+
+// Electron's accelerator grammar: any distinct modifiers, then one key.
+// Checked here because the menu refuses to build with a malformed one.
+const isAccelerator = (value) => {
+  const parts = value.split('+')
+  const key = parts.pop()
+  return isKey(key) && new Set(parts).size === parts.length && parts.every((part) => MODIFIERS.includes(part))
+}
+
+const commands = new EventEmitter()
+
+const available = () => definitions.filter((command) => !command.devOnly || !app.isPackaged)
+
+const find = (id) => {
+  const command = available().find((candidate) => candidate.id === id)
+  if (!command) throw new Error(`Unknown command '${id}'`)
+  return command
+}
+
+// A binding set to an empty string in settings unbinds the command. Bindings
+// edited by hand into something invalid fall back to the default.
+const accelerator = (id) => {
+  const overrides = settings.get('keybindings')
+  const override = Object.prototype.hasOwnProperty.call(overrides, id) ? overrides[id] : undefined
+  const value = override === '' || (override && isAccelerator(override)) ? override : find(id).accelerator
+  return value || null
+}
+
+// `null` restores the default binding and '' removes it.
+const setAccelerator = (id, value) => {
+  find(id)
+  if (value && !isAccelerator(value)) throw new Error(`'${value}' is not a valid shortcut`)
+  // Drops bindings for ids that no longer exist while it is at it.
+  const rest = Object.fromEntries(Object.entries(settings.get('keybindings'))
+    .filter(([key]) => key !== id && definitions.some((command) => command.id === key)))
+  settings.set('keybindings', value === null ? rest : { ...rest, [id]: value })
+  return accelerator(id)
+}
+
+const label = (id) => i18n.t(`command.${id}`)
+
+const list = () => available().map(({ id }) => ({ id, label: label(id), accelerator: accelerator(id) }))
+
+const execute = (id, { window = BrowserWindow.getFocusedWindow() } = {}) => {
+  const command = find(id)
+  if (command.renderer) {
+    if (window && !window.isDestroyed()) ipc.send(window.webContents, 'commands:invoke', { id })
+    return
+  }
+  command.run({ window })
+}
+
+settings.on('change', ({ key }) => {
+  if (key === 'keybindings') commands.emit('change')
+})
+i18n.on('change', () => commands.emit('change'))
+
+Object.assign(commands, { list, find, label, accelerator, setAccelerator, execute })
+
+module.exports = commands
@@ -0,0 +1,154 @@
+const { app, crashReporter } = require('electron')
+const fs = require('fs')
+const path = require('path')
+const http = require('./http')
+const ipc = require('./ipc')
+const { readJson, writeJsonAtomic } = require('./json-file')
+const logger = require('./logger')
+const settings = require('./settings')
+const windows = require('./windows')
+
+const log = logger.createLogger('crashes')
+
+const MAX_REPORTS = 20
+const RECENT_IPC = 10
+const COLLECT_DELAY = 1000
+const SESSION_SAVE_DELAY = 1000
+
+// Crashpad writes minidumps under dumps/; every dump we have seen gets a
+// metadata file under reports/. session.json holds the latest context so a
+// main-process crash can still be described on the next launch.
+const directory = () => path.join(app.getPath('userData'), 'crashes')
+const dumpsDirectory = () => path.join(directory(), 'dumps')
+const reportsDirectory = () => path.join(directory(), 'reports')
+const sessionFile = () => path.join(directory(), 'session.json')
+
+const session = { appVersion: app.getVersion(), activeWindow: null, lastIpc: [] }
+let previousSession = null
+let saveTimer = null
+
+const saveSession = () => {
+  clearTimeout(saveTimer)
+  saveTimer = setTimeout(() => writeJsonAtomic(sessionFile(), session), SESSION_SAVE_DELAY)
+}
+
+const annotate = (key, value) => {
+  // Crashpad truncates annotation values, so keep them short.
+  crashReporter.addExtraParameter(key, String(value).slice(0, 127))
+  saveSession()
+}
+
+const findDumps = (dir) => {
+  if (!fs.existsSync(dir)) return []
+  return fs.readdirSync(dir, { withFileTypes: true }).flatMap((entry) => {
+    const full = path.join(dir, entry.name)
+    if (entry.isDirectory()) return findDumps(full)
+    return entry.name.endsWith('.dmp') ? [full] : []
+  })
+}
+
+const reportFile = (id) => path.join(reportsDirectory(), `${id}.json`)
+
+const list = () => {
+  if (!fs.existsSync(reportsDirectory())) return []
+  return fs.readdirSync(reportsDirectory())
+    .filter((name) => name.endsWith('.json'))
+    .map((name) => readJson(path.join(reportsDirectory(), name), null))
+    .filter(Boolean)
+    .sort((a, b) => b.createdAt.localeCompare(a.createdAt))
+}
+
+const prune = () => {
+  for (const report of list().slice(MAX_REPORTS)) {
+    fs.rmSync(report.file, { force: true })
+    fs.rmSync(reportFile(report.id), { force: true })
+  }
+}
+
+const upload = async (id) => {
+  const report = readJson(reportFile(id), null)
+  if (!report) throw new Error(`Unknown crash report '${id}'`)
+  const endpoint = settings.get('crashReports.endpoint')
+  if (!endpoint) throw new Error('No crash report endpoint is configured')
+
+  const remoteId = await http.postMultipart(endpoint, {
+    product: app.getName(),
+    version: report.appVersion,
+    platform: process.platform,
+    process: report.process,
+    activeWindow: report.activeWindow || '',
+    lastIpc: report.lastIpc.join(','),
+  }, [{ name: 'upload_file_minidump', filename: path.basename(report.file), data: fs.readFileSync(report.file) }])
+
+  const uploaded = { ...report, uploadedAt: new Date().toISOString(), remoteId: remoteId.trim() || null }
+  writeJsonAtomic(reportFile(id), uploaded)
+  log.info('Uploaded crash report', { id, remoteId: uploaded.remoteId })
+  return uploaded
+}
+
+// Records metadata for dumps we have not seen yet. `context` describes the
+// session the crash happened in; `processType` comes from the gone event
+// when there is one.
+const collect = async ({ context = session, processType = 'unknown' } = {}) => {
+  fs.mkdirSync(reportsDirectory(), { recursive: true })
+  const fresh = []
+  for (const file of findDumps(dumpsDirectory())) {
+    const id = path.basename(file, '.dmp')
+    if (fs.existsSync(reportFile(id))) continue
+    const report = {
+      id,
+      file,
+      createdAt: fs.statSync(file).mtime.toISOString(),
+      process: processType,
+      appVersion: context.appVersion,
+      activeWindow: context.activeWindow,
+      lastIpc: context.lastIpc,
+      uploadedAt: null,
+      remoteId: null,
+    }
+    writeJsonAtomic(reportFile(id), report)
+    fresh.push(report)
+  }
+  prune()
+  if (fresh.length > 0) log.warn('Collected crash reports', { ids: fresh.map(({ id }) => id) })
+
+  if (settings.get('crashReports.upload') && settings.get('crashReports.endpoint')) {
+    for (const { id } of fresh) {
+      await upload(id).catch((error) => log.error('Crash report upload failed', { id, error }))
+    }
+  }
+  return fresh
+}
+
+const scheduleCollect = (processType) => {
+  setTimeout(() => collect({ processType }).catch((error) => log.error('Collecting crash reports failed', { error })), COLLECT_DELAY)
+}
+
+// Must run before the app is ready so Crashpad covers startup too.
+const start = () => {
+  previousSession = readJson(sessionFile(), null)
+  app.setPath('crashDumps', dumpsDirectory())
+  crashReporter.start({ uploadToServer: false, compress: true, globalExtra: { appVersion: app.getVersion() } })
+
+  app.on('browser-window-focus', (event, win) => {
+    const entry = windows.get(win.id)
+    session.activeWindow = entry ? `${entry.kind}#${win.id}` : `#${win.id}`
+    annotate('activeWindow', session.activeWindow)
+  })
+  ipc.calls.on('call', (name) => {
+    session.lastIpc = [...session.lastIpc, name].slice(-RECENT_IPC)
+    annotate('lastIpc', session.lastIpc.join(','))
+  })
+  app.on('render-process-gone', (event, contents, { reason }) => {
+    if (reason !== 'clean-exit') scheduleCollect('renderer')
+  })
+  app.on('child-process-gone', (event, { type, reason }) => {
+    if (reason !== 'clean-exit') scheduleCollect(type)
+  })
+
+  // Anything left from the previous run crashed in that session.
+  app.whenReady().then(() => collect({ context: previousSession || session, processType: 'browser' }))
+    .catch((error) => log.error('Collecting crash reports failed', { error }))
+}
+
+module.exports = { start, collect, list, upload, directory }
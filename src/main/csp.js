@@ -0,0 +1,46 @@
+const { app, session } = require('electron')
+const ipc = require('./ipc')
+const logger = require('./logger')
+
+const log = logger.createLogger('csp')
+
+const production = {
+  'default-src': ["'self'"],
+  'script-src': ["'self'"],
+  'style-src': ["'self'"],
+  'img-src': ["'self'", 'data:'],
+  'font-src': ["'self'"],
+  'connect-src': ["'self'"],
+  'object-src': ["'none'"],
+  'base-uri': ["'none'"],
+  'form-action': ["'none'"],
+  'frame-ancestors': ["'none'"],
+}
+
+// Development additionally lets the renderer reach local dev servers, e.g.
+// for live reload. Scripts stay restricted to 'self' in both.
+const development = {
+  ...production,
+  'connect-src': ["'self'", 'http://localhost:*', 'ws://localhost:*'],
+}
+
+const serialize = (policy) =>
+  Object.entries(policy).map(([directive, sources]) => [directive, ...sources].join(' ')).join('; ')
+
+const policy = () => serialize(app.isPackaged ? production : development)
+
+const install = () => {
+  session.defaultSession.webRequest.onHeadersReceived((details, callback) => {
+    const responseHeaders = Object.fromEntries(
+      Object.entries(details.responseHeaders || {}).filter(([name]) => name.toLowerCase() !== 'content-security-policy'),
+    )
+    responseHeaders['Content-Security-Policy'] = [policy()]
+    callback({ responseHeaders })
+  })
+
+  ipc.on('csp:violation', (context, violation) => {
+    log.warn(`Violated ${violation.directive}`, violation)
+  })
+}
+
+module.exports = { policy, install }
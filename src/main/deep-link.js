@@ -0,0 +1,77 @@
+const { app } = require('electron')
+const path = require('path')
+const ipc = require('./ipc')
+const logger = require('./logger')
+const { check } = require('./schema')
+const views = require('./views')
+const windows = require('./windows')
+
+const log = logger.createLogger('deep-link')
+
+const SCHEME = 'zaphnath'
+const MAX_LENGTH = 2048
+
+const navigate = (view) => {
+  const existing = windows.find('main')
+  if (!existing) return windows.open('main', { view })
+  const { win } = existing
+  const deliver = () => {
+    windows.focus(win)
+    ipc.send(win.webContents, 'app:navigate', { view })
+  }
+  // A window opened moments ago (a cold start, or a second instance that
+  // found none) has no listener yet, and showing it early would flash an
+  // unpainted window.
+  if (win.webContents.isLoading()) win.webContents.once('did-finish-load', deliver)
+  else deliver()
+}
+
+// The only actions a link can trigger. Each declares the exact query
+// parameters it accepts; anything else rejects the whole link.
+const routes = {
+  open: {
+    params: { type: 'object', properties: { view: { enum: views } } },
+    run: ({ view }) => navigate(view),
+  },
+}
+
+const isDeepLink = (url) => typeof url === 'string' && url.toLowerCase().startsWith(`${SCHEME}:`)
+
+const parse = (url) => {
+  if (url.length > MAX_LENGTH) throw new Error('Link is too long')
+  const parsed = new URL(url)
+  if (parsed.protocol !== `${SCHEME}:`) throw new Error(`Not a ${SCHEME}: link`)
+  const action = parsed.hostname
+  const route = Object.prototype.hasOwnProperty.call(routes, action) && routes[action]
+  if (!route) throw new Error(`Unknown action '${action}'`)
+  if (parsed.pathname && parsed.pathname !== '/') throw new Error('Unexpected path in link')
+
+  const params = {}
+  for (const [key, value] of parsed.searchParams) {
+    if (Object.prototype.hasOwnProperty.call(params, key)) throw new Error(`Duplicate parameter '${key}'`)
+    params[key] = value
+  }
+  check(route.params, params, action)
+  return { action, params }
+}
+
+const handle = (url) => {
+  try {
+    const { action, params } = parse(url)
+    log.info('Handling link', { action, params })
+    routes[action].run(params)
+  } catch (error) {
+    log.warn('Rejected link', { url: url.slice(0, MAX_LENGTH), error: error.message })
+  }
+}
+
+// Unpackaged runs are launched as `electron <app path>`, so the OS needs the
+// app path to relaunch us with the link.
+const register = () => {
+  const registered = process.defaultApp
+    ? app.setAsDefaultProtocolClient(SCHEME, process.execPath, [path.resolve(process.argv[1])])
+    : app.setAsDefaultProtocolClient(SCHEME)
+  if (!registered) log.warn(`Could not register as the ${SCHEME}: handler`)
+}
+
+module.exports = { SCHEME, isDeepLink, parse, handle, register }
@@ -0,0 +1,126 @@
+const { app, clipboard, dialog } = require('electron')
+const { execFileSync } = require('child_process')
+const fs = require('fs')
+const os = require('os')
+const path = require('path')
+const i18n = require('./i18n')
+const { readJson } = require('./json-file')
+const logger = require('./logger')
+const native = require('./native')
+const settings = require('./settings')
+const sidecar = require('./sidecar')
+
+const log = logger.createLogger('diagnostics')
+
+const root = path.join(__dirname, '..', '..')
+
+let build = null
+
+const gitCommit = () => {
+  try {
+    return execFileSync('git', ['rev-parse', '--short', 'HEAD'], { cwd: root, stdio: ['ignore', 'pipe', 'ignore'] }).toString().trim()
+  } catch {
+    return 'unknown'
+  }
+}
+
+// Packaged builds carry build-info.json from the forge hook; unpackaged runs
+// ask git for the commit instead.
+const buildInfo = () => {
+  if (!build) build = readJson(path.join(root, 'build-info.json'), null) || { version: app.getVersion(), commit: gitCommit() }
+  return build
+}
+
+const snapshot = () => {
+  const { version, commit, builtAt } = buildInfo()
+  return {
+    generatedAt: new Date().toISOString(),
+    app: { name: app.getName(), version, commit, builtAt: builtAt || null, packaged: app.isPackaged, locale: app.getLocale(), native: native.backend(), sidecar: sidecar.status().state },
+    os: {
+      platform: process.platform,
+      arch: process.arch,
+      release: os.release(),
+      cpus: os.cpus().length,
+      cpuModel: (os.cpus()[0] || {}).model || 'unknown',
+      totalMemory: os.totalmem(),
+      freeMemory: os.freemem(),
+    },
+    versions: {
+      electron: process.versions.electron,
+      chrome: process.versions.chrome,
+      node: process.versions.node,
+      v8: process.versions.v8,
+    },
+    gpu: app.getGPUFeatureStatus(),
+    metrics: app.getAppMetrics().map(({ pid, type, name, cpu, memory }) => ({
+      pid,
+      type,
+      name: name || null,
+      cpu: cpu.percentCPUUsage,
+      memory: memory.workingSetSize * 1024,
+      peakMemory: memory.peakWorkingSetSize * 1024,
+    })),
+    paths: {
+      app: app.getAppPath(),
+      userData: app.getPath('userData'),
+      logs: logger.directory(),
+      crashes: app.getPath('crashDumps'),
+      temp: app.getPath('temp'),
+    },
+    settings: settings.get(),
+  }
+}
+
+const megabytes = (bytes) => `${(bytes / 1024 / 1024).toFixed(1)} MB`
+
+const report = (data = snapshot()) => {
+  const lines = [
+    `Zaphnath ${data.app.version} (${data.app.commit})${data.app.packaged ? '' : ' [development]'}`,
+    `Generated ${data.generatedAt}`,
+    '',
+    `OS: ${data.os.platform} ${data.os.release} ${data.os.arch}, ${data.os.cpus} × ${data.os.cpuModel}`,
+    `Memory: ${megabytes(data.os.freeMemory)} free of ${megabytes(data.os.totalMemory)}`,
+    `Versions: ${Object.entries(data.versions).map(([name, value]) => `${name} ${value}`).join(', ')}`,
+    `Locale: ${data.app.locale}`,
+    `Native addon: ${data.app.native}`,
+    `Sidecar: ${data.app.sidecar}`,
+    '',
+    'GPU:',
+    ...Object.entries(data.gpu).map(([feature, state]) => `  ${feature}: ${state}`),
+    '',
+    'Processes:',
+    ...data.metrics.map(({ pid, type, name, cpu, memory }) =>
+      `  ${pid} ${type}${name ? ` (${name})` : ''}: ${cpu.toFixed(1)}% CPU, ${megabytes(memory)}`),
+    '',
+    'Paths:',
+    ...Object.entries(data.paths).map(([name, value]) => `  ${name}: ${value}`),
+    '',
+    'Settings:',
+    ...Object.entries(data.settings).map(([key, value]) => `  ${key}: ${JSON.stringify(value)}`),
+  ]
+  return `${lines.join('\n')}\n`
+}
+
+const copyReport = () => {
+  clipboard.writeText(report())
+}
+
+const bundle = () => ({
+  ...snapshot(),
+  logs: Object.fromEntries(logger.files().map((file) => [path.basename(file), fs.readFileSync(file, 'utf8')])),
+})
+
+const exportBundle = async (window) => {
+  const stamp = new Date().toISOString().replace(/[:.]/g, '-')
+  const { canceled, filePath } = await dialog.showSaveDialog(window, {
+    title: i18n.t('dialog.exportDiagnostics.title'),
+    defaultPath: path.join(app.getPath('downloads'), `zaphnath-diagnostics-${stamp}.json`),
+    filters: [{ name: 'JSON', extensions: ['json'] }],
+  })
+  if (canceled || !filePath) return null
+  fs.writeFileSync(filePath, JSON.stringify(bundle(), null, 2))
+  log.info('Exported diagnostics bundle', { filePath })
+  return filePath
+}
+
+module.exports = { buildInfo, snapshot, report, copyReport, bundle, exportBundle }
@@ -0,0 +1,107 @@
+const commands = require('./commands')
+const crashes = require('./crashes')
+const diagnostics = require('./diagnostics')
+const i18n = require('./i18n')
+const jobTasks = require('./job-tasks')
+const jobs = require('./jobs')
+const ipc = require('./ipc')
+const logger = require('./logger')
+const native = require('./native')
+const { check } = require('./schema')
+const settings = require('./settings')
+const sidecar = require('./sidecar')
+const theme = require('./theme')
+const tray = require('./tray')
+const updater = require('./updater')
+const windows = require('./windows')
+
+const MAX_RENDERER_FIELDS = 8 * 1024
+
+// Renderer fields go into the log line as they are, so oversized ones are
+// replaced with a truncated JSON string rather than filling the log file.
+const rendererFields = (fields) => {
+  if (!fields) return fields
+  let json
+  try {
+    json = JSON.stringify(fields)
+  } catch (error) {
+    return { fields: '[unserializable]' }
+  }
+  return json.length > MAX_RENDERER_FIELDS ? { fields: json.slice(0, MAX_RENDERER_FIELDS), truncated: true } : fields
+}
+
+const registerHandlers = () => {
+  ipc.handle('ping', () => 'pong')
+
+  ipc.handle('commands:list', () => commands.list())
+  ipc.handle('commands:setAccelerator', (context, id, accelerator) => commands.setAccelerator(id, accelerator))
+  ipc.handle('commands:execute', ({ window }, id) => {
+    commands.execute(id, { window })
+    return null
+  })
+
+  ipc.handle('crashes:list', () => crashes.list())
+  ipc.handle('crashes:upload', (context, id) => crashes.upload(id))
+
+  ipc.handle('diagnostics:snapshot', () => diagnostics.snapshot())
+  ipc.handle('diagnostics:copyReport', () => {
+    diagnostics.copyReport()
+    return null
+  })
+
+  ipc.handle('i18n:getCatalog', () => i18n.catalog())
+  i18n.on('change', () => windows.broadcast('i18n:changed', i18n.catalog()))
+
+  ipc.handle('jobs:start', (context, task, params) => jobs.start(task, check(jobTasks[task], params, 'jobs:start argument 1')))
+  ipc.handle('jobs:cancel', (context, id) => jobs.cancel(id))
+  ipc.handle('jobs:list', () => jobs.list())
+  jobs.on('update', (job) => windows.broadcast('jobs:update', job))
+
+  ipc.on('log:write', ({ sender, window }, level, message, fields) => {
+    const entry = window && windows.get(window.id)
+    const scope = entry ? `${entry.kind}#${window.id}` : `webContents#${sender.id}`
+    logger.log(level, scope, message, rendererFields(fields), 'renderer')
+  })
+
+  ipc.handle('native:backend', () => native.backend())
+  ipc.handle('native:sha256', (context, data) => native.sha256(data))
+  ipc.handle('native:gzip', (context, data, level) => native.gzip(data, level))
+  ipc.handle('native:gunzip', (context, data) => native.gunzip(data))
+  ipc.handle('native:inspectFile', ({ window }) => native.chooseAndInspect(window))
+
+  ipc.handle('settings:get', (context, key) => settings.get(key))
+  ipc.handle('settings:set', (context, key, value) => settings.setFromRenderer(key, value))
+  settings.on('change', ({ key, value }) => windows.broadcast('settings:changed', { key, value }))
+
+  ipc.handle('sidecar:call', ({ sender }, method, params, options) => sidecar.callFor(sender, method, params, options))
+  ipc.handle('sidecar:cancel', ({ sender }, requestId) => sidecar.cancelFor(sender, requestId))
+  ipc.handle('sidecar:getStatus', () => sidecar.status())
+  sidecar.on('status', (status) => windows.broadcast('sidecar:status', status))
+
+  ipc.handle('theme:get', () => theme.current())
+  theme.on('change', (state) => {
+    for (const { win } of windows.list()) win.setBackgroundColor(theme.backgroundColor())
+    windows.broadcast('theme:changed', state)
+  })
+
+  ipc.handle('tray:setStatus', (context, status) => {
+    tray.setStatus(status)
+    return null
+  })
+
+  ipc.handle('updater:check', () => updater.check())
+  ipc.handle('updater:getStatus', () => updater.status())
+  ipc.handle('updater:install', () => {
+    updater.install()
+    return null
+  })
+  updater.on('status', (status) => windows.broadcast('updater:status', status))
+
+  ipc.handle('windows:open', (context, kind, params) => windows.open(kind, params).id)
+  ipc.handle('windows:current', ({ window }) => {
+    const { id, kind, params } = windows.get(window.id)
+    return { id, kind, params }
+  })
+}
+
+module.exports = { registerHandlers }
@@ -0,0 +1,97 @@
+const crypto = require('crypto')
+const fs = require('fs')
+const http = require('http')
+const https = require('https')
+
+const MAX_REDIRECTS = 5
+// Applies to socket inactivity, so slow but steady downloads still finish.
+const TIMEOUT = 30 * 1000
+
+const clientFor = (url) => {
+  const { protocol } = new URL(url)
+  if (protocol === 'https:') return https
+  if (protocol === 'http:') return http
+  throw new Error(`Unsupported protocol in ${url}`)
+}
+
+const readBody = async (stream) => {
+  const chunks = []
+  for await (const chunk of stream) chunks.push(chunk)
+  return Buffer.concat(chunks)
+}
+
+const get = (url, redirects = 0) => new Promise((resolve, reject) => {
+  const client = clientFor(url)
+  const request = client.get(url, (response) => {
+    const { statusCode, headers } = response
+    if (statusCode >= 300 && statusCode < 400 && headers.location) {
+      response.resume()
+      if (redirects >= MAX_REDIRECTS) return reject(new Error(`Too many redirects fetching ${url}`))
+      return resolve(get(new URL(headers.location, url).href, redirects + 1))
+    }
+    if (statusCode !== 200) {
+      response.resume()
+      return reject(new Error(`GET ${url} failed with status ${statusCode}`))
+    }
+    resolve(response)
+  })
+  request.setTimeout(TIMEOUT, () => request.destroy(new Error(`GET ${url} timed out`)))
+  request.on('error', reject)
+})
+
+const getJson = async (url) => JSON.parse((await readBody(await get(url))).toString('utf8'))
+
+// Streams `url` into `file`, hashing as it goes. `onProgress` receives the
+// fraction downloaded when the server sends a content length.
+const download = async (url, file, onProgress = () => {}) => {
+  const response = await get(url)
+  const total = Number(response.headers['content-length']) || 0
+  const hash = crypto.createHash('sha256')
+  const output = fs.createWriteStream(file)
+  let received = 0
+  try {
+    for await (const chunk of response) {
+      hash.update(chunk)
+      received += chunk.length
+      if (!output.write(chunk)) await new Promise((resolve) => output.once('drain', resolve))
+      if (total) onProgress(received / total)
+    }
+  } finally {
+    await new Promise((resolve) => output.end(resolve))
+  }
+  return { bytes: received, sha256: hash.digest('hex') }
+}
+
+// Posts `fields` and `files` ({ name, filename, data }) as
+// multipart/form-data and resolves with the response body as text.
+const postMultipart = (url, fields, files = []) => new Promise((resolve, reject) => {
+  const client = clientFor(url)
+  const boundary = `----zaphnath${crypto.randomBytes(12).toString('hex')}`
+  const parts = Object.entries(fields).map(([name, value]) =>
+    Buffer.from(`--${boundary}\r\nContent-Disposition: form-data; name="${name}"\r\n\r\n${value}\r\n`))
+  for (const { name, filename, data } of files) {
+    parts.push(
+      Buffer.from(`--${boundary}\r\nContent-Disposition: form-data; name="${name}"; filename="${filename}"\r\nContent-Type: application/octet-stream\r\n\r\n`),
+      data,
+      Buffer.from('\r\n'),
+    )
+  }
+  parts.push(Buffer.from(`--${boundary}--\r\n`))
+  const body = Buffer.concat(parts)
+
+  const request = client.request(url, {
+    method: 'POST',
+    headers: { 'content-type': `multipart/form-data; boundary=${boundary}`, 'content-length': body.length },
+  }, async (response) => {
+    const text = (await readBody(response)).toString('utf8')
+    if (response.statusCode < 200 || response.statusCode >= 300) {
+      return reject(new Error(`POST ${url} failed with status ${response.statusCode}`))
+    }
+    resolve(text)
+  })
+  request.setTimeout(TIMEOUT, () => request.destroy(new Error(`POST ${url} timed out`)))
+  request.on('error', reject)
+  request.end(body)
+})
+
+module.exports = { get, getJson, download, postMultipart }
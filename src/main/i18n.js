@@ -0,0 +1,69 @@
+const { app } = require('electron')
+const { EventEmitter } = require('events')
+const fs = require('fs')
+const path = require('path')
+const settings = require('./settings')
+
+const FALLBACK = 'en'
+
+const directory = path.join(__dirname, '..', '..', 'locales')
+
+const catalogs = Object.fromEntries(fs.readdirSync(directory)
+  .filter((name) => name.endsWith('.json'))
+  .map((name) => [path.basename(name, '.json'), JSON.parse(fs.readFileSync(path.join(directory, name), 'utf8'))]))
+
+const i18n = new EventEmitter()
+
+let locale = FALLBACK
+
+// "am-ET" falls back to "am", anything unknown to English.
+const resolve = (requested) => [requested, requested.split('-')[0]].find((candidate) => catalogs[candidate]) || FALLBACK
+
+const detect = () => {
+  const preferred = settings.get('general.locale')
+  return resolve(preferred === 'system' ? app.getLocale() : preferred)
+}
+
+const messages = () => ({ ...catalogs[FALLBACK], ...catalogs[locale] })
+
+// Messages interpolate {name} placeholders. A message can instead be an
+// object keyed by Intl.PluralRules category, selected by params.count.
+// src/renderer/i18n.js implements the same rules for the renderer.
+const format = (code, message, params = {}) => {
+  if (message === undefined) return undefined
+  if (typeof message === 'object') {
+    message = message[new Intl.PluralRules(code).select(params.count)] || message.other
+  }
+  return message.replace(/\{(\w+)\}/g, (match, name) => {
+    if (!(name in params)) return match
+    const value = params[name]
+    return typeof value === 'number' ? new Intl.NumberFormat(code).format(value) : String(value)
+  })
+}
+
+const t = (key, params) => format(locale, messages()[key], params) || key
+
+const catalog = () => ({
+  locale,
+  dir: messages()['meta.dir'],
+  messages: messages(),
+  available: Object.entries(catalogs).map(([code, entries]) => ({ code, name: entries['meta.name'] })),
+})
+
+const update = () => {
+  const next = detect()
+  if (next === locale) return
+  locale = next
+  i18n.emit('change', locale)
+}
+
+const start = () => {
+  locale = detect()
+  settings.on('change', ({ key }) => {
+    if (key === 'general.locale') update()
+  })
+}
+
+Object.assign(i18n, { t, format, catalog, locale: () => locale, start })
+
+module.exports = i18n
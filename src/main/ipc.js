@@ -0,0 +1,104 @@
+const { BrowserWindow, ipcMain } = require('electron')
+const { EventEmitter } = require('events')
+const channels = require('./channels')
+const logger = require('./logger')
+const { check } = require('./schema')
+
+const log = logger.createLogger('ipc')
+
+// `ipc.on` is taken by send channels, so call notifications get their own
+// emitter: it emits 'call' with the channel name for every accepted renderer
+// message.
+const calls = new EventEmitter()
+
+const dispatched = new Set()
+const handlers = new Map()
+const windowHandlers = new Map()
+
+const lookup = (name) => {
+  const channel = channels[name]
+  if (!channel) throw new Error(`Unknown IPC channel '${name}'`)
+  return channel
+}
+
+const checkArgs = (name, channel, args) => {
+  if (args.length > channel.args.length) {
+    throw new TypeError(`${name} takes ${channel.args.length} argument(s), got ${args.length}`)
+  }
+  channel.args.forEach((schema, index) => check(schema, args[index], `${name} argument ${index}`))
+}
+
+const resolveHandler = (name, sender) => {
+  const scoped = windowHandlers.get(name)
+  return (scoped && scoped.get(sender.id)) || handlers.get(name)
+}
+
+// ipcMain only allows one handler per channel, so each channel gets a single
+// dispatcher that picks the window-scoped handler first and the global one
+// otherwise.
+const ensureDispatcher = (name) => {
+  const channel = lookup(name)
+  if (channel.kind) throw new Error(`'${name}' is a ${channel.kind} channel and cannot be handled`)
+  if (dispatched.has(name)) return
+  dispatched.add(name)
+  ipcMain.handle(name, async (event, ...args) => {
+    const handler = resolveHandler(name, event.sender)
+    if (!handler) throw new Error(`No handler for '${name}' in this window`)
+    checkArgs(name, channel, args)
+    calls.emit('call', name)
+    const context = { event, sender: event.sender, window: BrowserWindow.fromWebContents(event.sender) }
+    const result = await handler(context, ...args)
+    return check(channel.returns, result, `${name} result`)
+  })
+}
+
+const handle = (name, handler) => {
+  if (handlers.has(name)) throw new Error(`A handler for '${name}' is already registered`)
+  ensureDispatcher(name)
+  handlers.set(name, handler)
+}
+
+const handleForWindow = (win, name, handler) => {
+  ensureDispatcher(name)
+  if (!windowHandlers.has(name)) windowHandlers.set(name, new Map())
+  const scoped = windowHandlers.get(name)
+  const id = win.webContents.id
+  if (scoped.has(id)) throw new Error(`Window ${win.id} already has a handler for '${name}'`)
+  scoped.set(id, handler)
+  win.webContents.once('destroyed', () => scoped.delete(id))
+}
+
+// Fire-and-forget channels (`kind: 'send'`) have no reply, so a payload that
+// fails validation is dropped with a warning instead of rejected.
+const on = (name, listener) => {
+  const channel = lookup(name)
+  if (channel.kind !== 'send') throw new Error(`'${name}' is not a send channel`)
+  ipcMain.on(name, (event, ...args) => {
+    try {
+      checkArgs(name, channel, args)
+    } catch (error) {
+      log.warn(`Dropped invalid '${name}' message`, { error: error.message })
+      return
+    }
+    calls.emit('call', name)
+    const context = { event, sender: event.sender, window: BrowserWindow.fromWebContents(event.sender) }
+    listener(context, ...args)
+  })
+}
+
+const send = (webContents, name, payload) => {
+  const channel = lookup(name)
+  if (channel.kind !== 'event') throw new Error(`'${name}' is not an event channel`)
+  check(channel.payload, payload, `${name} payload`)
+  webContents.send(name, payload)
+}
+
+const manifest = Object.entries(channels)
+  .filter(([, channel]) => channel.bridge)
+  .map(([name, channel]) => ({ name, kind: channel.kind || 'invoke', bridge: channel.bridge }))
+
+ipcMain.on('ipc:manifest', (event) => {
+  event.returnValue = manifest
+})
+
+module.exports = { calls, handle, handleForWindow, on, send }
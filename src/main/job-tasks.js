@@ -0,0 +1,17 @@
+// Params each job task accepts, so `jobs:start` rejects bad payloads before
+// they reach a worker. Keep in step with src/worker/tasks.js.
+module.exports = {
+  countPrimes: {
+    type: 'object',
+    properties: {
+      limit: { type: 'number', integer: true, minimum: 0, maximum: 1e9 },
+    },
+  },
+  wait: {
+    type: 'object',
+    properties: {
+      ms: { type: 'number', minimum: 0, maximum: 10 * 60 * 1000 },
+      steps: { type: 'number', integer: true, minimum: 1, maximum: 1000, optional: true },
+    },
+  },
+}
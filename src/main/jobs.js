@@ -0,0 +1,159 @@
+const { utilityProcess } = require('electron')
+const { EventEmitter } = require('events')
+const path = require('path')
+const logger = require('./logger')
+const settings = require('./settings')
+
+const log = logger.createLogger('jobs')
+
+const workerPath = path.join(__dirname, '..', 'worker', 'index.js')
+
+// A worker that ignores a cancel for this long is killed.
+const CANCEL_GRACE = 2000
+const MAX_FINISHED = 50
+
+const FINISHED = ['completed', 'failed', 'cancelled']
+
+// Emits 'update' with a job snapshot whenever a job changes state or
+// reports progress.
+const jobs = new EventEmitter()
+
+const all = new Map()
+const queue = []
+// Worker processes, each with the job it is running or null when idle.
+const workers = new Set()
+let nextId = 1
+
+const snapshot = ({ id, task, state, progress, partial, result, error, createdAt, startedAt, finishedAt }) =>
+  ({ id, task, state, progress, partial, result, error, createdAt, startedAt, finishedAt })
+
+const update = (job, patch) => {
+  Object.assign(job, patch)
+  jobs.emit('update', snapshot(job))
+}
+
+const prune = () => {
+  const finished = [...all.values()].filter(({ state }) => FINISHED.includes(state))
+  for (const job of finished.slice(0, Math.max(finished.length - MAX_FINISHED, 0))) all.delete(job.id)
+}
+
+const finish = (job, state, patch = {}) => {
+  if (FINISHED.includes(job.state)) return
+  clearTimeout(job.killTimer)
+  update(job, { ...patch, state, finishedAt: new Date().toISOString() })
+  log[state === 'failed' ? 'warn' : 'info'](`Job ${state}`, { id: job.id, task: job.task, ...(patch.error ? { error: patch.error } : {}) })
+  prune()
+}
+
+const release = (worker) => {
+  worker.job = null
+  pump()
+}
+
+const onMessage = (worker, message) => {
+  const { job } = worker
+  if (!job || message.jobId !== job.id) return
+  switch (message.type) {
+    case 'progress':
+      if (job.state === 'running') update(job, { progress: message.progress, partial: message.partial === undefined ? null : message.partial })
+      return
+    case 'done':
+      finish(job, 'completed', { progress: 1, result: message.result === undefined ? null : message.result })
+      return release(worker)
+    case 'failed':
+      finish(job, 'failed', { error: message.error.message })
+      return release(worker)
+    case 'cancelled':
+      finish(job, 'cancelled')
+      return release(worker)
+    default:
+      log.warn('Unknown message from job worker', { type: message.type })
+  }
+}
+
+const spawnWorker = () => {
+  const worker = { process: utilityProcess.fork(workerPath, [], { serviceName: 'Zaphnath Job Worker' }), job: null }
+  workers.add(worker)
+  worker.process.on('message', (message) => onMessage(worker, message))
+  // A crash fails the job it was running instead of leaving it hanging; the
+  // queue carries on with a fresh worker.
+  worker.process.on('exit', (code) => {
+    workers.delete(worker)
+    const { job } = worker
+    if (job) {
+      if (job.cancelRequested) finish(job, 'cancelled')
+      else finish(job, 'failed', { error: `Worker exited unexpectedly with code ${code}` })
+      log.error('Job worker exited', { code, job: job.id })
+    }
+    pump()
+  })
+  return worker
+}
+
+const concurrency = () => settings.get('jobs.concurrency')
+
+const pump = () => {
+  while (queue.length > 0) {
+    const idle = [...workers].find((worker) => !worker.job)
+    const busy = [...workers].filter((worker) => worker.job).length
+    if (!idle && busy >= concurrency()) return
+    const worker = idle || spawnWorker()
+    const job = queue.shift()
+    worker.job = job
+    update(job, { state: 'running', startedAt: new Date().toISOString() })
+    worker.process.postMessage({ type: 'run', jobId: job.id, task: job.task, params: job.params })
+  }
+}
+
+const start = (task, params) => {
+  const job = {
+    id: nextId++,
+    task,
+    params,
+    state: 'queued',
+    progress: 0,
+    partial: null,
+    result: null,
+    error: null,
+    createdAt: new Date().toISOString(),
+    startedAt: null,
+    finishedAt: null,
+  }
+  all.set(job.id, job)
+  queue.push(job)
+  jobs.emit('update', snapshot(job))
+  pump()
+  return snapshot(job)
+}
+
+const cancel = (id) => {
+  const job = all.get(id)
+  if (!job || FINISHED.includes(job.state)) return false
+  if (job.state === 'queued') {
+    queue.splice(queue.indexOf(job), 1)
+    finish(job, 'cancelled')
+    return true
+  }
+  const worker = [...workers].find((candidate) => candidate.job === job)
+  job.cancelRequested = true
+  worker.process.postMessage({ type: 'cancel', jobId: id })
+  job.killTimer = setTimeout(() => worker.process.kill(), CANCEL_GRACE)
+  return true
+}
+
+const list = () => [...all.values()].map(snapshot)
+
+const get = (id) => (all.has(id) ? snapshot(all.get(id)) : null)
+
+// Cancels anything unfinished and shuts the workers down, e.g. on quit.
+const stop = () => {
+  for (const job of queue.splice(0)) finish(job, 'cancelled')
+  for (const worker of workers) {
+    if (worker.job) worker.job.cancelRequested = true
+    worker.process.kill()
+  }
+}
+
+Object.assign(jobs, { start, cancel, list, get, stop })
+
+module.exports = jobs
@@ -0,0 +1,28 @@
+const fs = require('fs')
+const path = require('path')
+
+const readJson = (file, fallback) => {
+  try {
+    return JSON.parse(fs.readFileSync(file, 'utf8'))
+  } catch (error) {
+    if (error.code !== 'ENOENT' && !(error instanceof SyntaxError)) throw error
+    return fallback
+  }
+}
+
+// Write to a sibling temp file and rename over the target so a crash midway
+// never leaves a truncated file behind.
+const writeJsonAtomic = (file, data) => {
+  fs.mkdirSync(path.dirname(file), { recursive: true })
+  const temp = `${file}.${process.pid}.tmp`
+  const fd = fs.openSync(temp, 'w')
+  try {
+    fs.writeSync(fd, `${JSON.stringify(data, null, 2)}\n`)
+    fs.fsyncSync(fd)
+  } finally {
+    fs.closeSync(fd)
+  }
+  fs.renameSync(temp, file)
+}
+
+module.exports = { readJson, writeJsonAtomic }
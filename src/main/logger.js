@@ -0,0 +1,74 @@
+const { app } = require('electron')
+const fs = require('fs')
+const path = require('path')
+
+const LEVELS = { debug: 10, info: 20, warn: 30, error: 40 }
+const MAX_BYTES = 5 * 1024 * 1024
+const MAX_FILES = 5
+const FILE_NAME = 'main.log'
+
+const threshold = () => LEVELS[process.env.ZAPHNATH_LOG_LEVEL] || (app.isPackaged ? LEVELS.info : LEVELS.debug)
+
+const directory = () => path.join(app.getPath('userData'), 'logs')
+
+const file = () => path.join(directory(), FILE_NAME)
+
+const rotated = (index) => path.join(directory(), FILE_NAME.replace(/\.log$/, `.${index}.log`))
+
+// main.log rolls over to main.1.log, main.1.log to main.2.log, and so on;
+// the oldest file past MAX_FILES is dropped.
+const rotate = () => {
+  fs.rmSync(rotated(MAX_FILES - 1), { force: true })
+  for (let index = MAX_FILES - 2; index >= 1; index -= 1) {
+    if (fs.existsSync(rotated(index))) fs.renameSync(rotated(index), rotated(index + 1))
+  }
+  fs.renameSync(file(), rotated(1))
+}
+
+const serialize = (value) => {
+  if (value instanceof Error) return { name: value.name, message: value.message, stack: value.stack }
+  return value
+}
+
+const write = (entry) => {
+  const line = `${JSON.stringify(entry, (key, value) => serialize(value))}\n`
+  try {
+    fs.mkdirSync(directory(), { recursive: true })
+    const size = fs.existsSync(file()) ? fs.statSync(file()).size : 0
+    if (size > 0 && size + Buffer.byteLength(line) > MAX_BYTES) rotate()
+    fs.appendFileSync(file(), line)
+  } catch (error) {
+    console.error('Failed to write log entry', error)
+  }
+}
+
+const log = (level, scope, message, fields = {}, source = 'main') => {
+  if (LEVELS[level] < threshold()) return
+  // Fields go first so a caller (or a renderer) cannot overwrite the
+  // envelope and pass its entry off as coming from somewhere else.
+  const entry = { ...fields, time: new Date().toISOString(), level, process: source, scope, message }
+  write(entry)
+  if (!app.isPackaged) {
+    const method = level === 'debug' ? 'log' : level
+    console[method](`[${level}] ${scope}: ${message}`, ...(Object.keys(fields).length > 0 ? [fields] : []))
+  }
+}
+
+const createLogger = (scope) => ({
+  debug: (message, fields) => log('debug', scope, message, fields),
+  info: (message, fields) => log('info', scope, message, fields),
+  warn: (message, fields) => log('warn', scope, message, fields),
+  error: (message, fields) => log('error', scope, message, fields),
+  child: (name) => createLogger(`${scope}:${name}`),
+})
+
+// Log files oldest first, for bundling into diagnostics.
+const files = () => {
+  if (!fs.existsSync(directory())) return []
+  return fs.readdirSync(directory())
+    .filter((name) => name.endsWith('.log'))
+    .map((name) => path.join(directory(), name))
+    .sort((a, b) => fs.statSync(a).mtimeMs - fs.statSync(b).mtimeMs)
+}
+
+module.exports = { LEVELS, createLogger, log, directory, files }
@@ -0,0 +1,62 @@
+const { app, Menu } = require('electron')
+const commands = require('./commands')
+const i18n = require('./i18n')
+
+const item = (id) => {
+  return {
+    label: commands.label(id),
+    accelerator: commands.accelerator(id) || undefined,
+    click: (menuItem, window) => commands.execute(id, { window }),
+  }
+}
+
+const has = (id) => commands.list().some((command) => command.id === id)
+
+const template = () => {
+  const isMac = process.platform === 'darwin'
+  return [
+    ...(isMac
+      ? [{
+          label: app.name,
+          submenu: [item('app.about'), item('app.checkForUpdates'), { type: 'separator' }, item('app.preferences'), { type: 'separator' }, { role: 'hide' }, { role: 'hideOthers' }, { role: 'unhide' }, { type: 'separator' }, item('app.quit')],
+        }]
+      : []),
+    {
+      label: i18n.t('menu.file'),
+      submenu: [
+        item('window.new'),
+        ...(isMac ? [] : [{ type: 'separator' }, item('app.preferences'), { type: 'separator' }, item('app.quit')]),
+      ],
+    },
+    { role: 'editMenu' },
+    {
+      label: i18n.t('menu.view'),
+      submenu: [
+        item('view.commandPalette'),
+        { type: 'separator' },
+        item('view.reload'),
+        ...(has('view.toggleDevTools') ? [item('view.toggleDevTools')] : []),
+        { type: 'separator' },
+        { role: 'togglefullscreen' },
+      ],
+    },
+    { role: 'windowMenu' },
+    {
+      role: 'help',
+      label: i18n.t('menu.help'),
+      submenu: [
+        item('help.openLogs'),
+        item('help.exportDiagnostics'),
+        ...(isMac ? [] : [{ type: 'separator' }, item('app.checkForUpdates'), item('app.about')]),
+      ],
+    },
+  ]
+}
+
+const install = () => {
+  Menu.setApplicationMenu(Menu.buildFromTemplate(template()))
+}
+
+commands.on('change', install)
+
+module.exports = { install }
@@ -0,0 +1,107 @@
+const { dialog } = require('electron')
+const crypto = require('crypto')
+const fs = require('fs')
+const path = require('path')
+const { promisify } = require('util')
+const zlib = require('zlib')
+const i18n = require('./i18n')
+const logger = require('./logger')
+
+const log = logger.createLogger('native')
+
+// Built by `npm run build:native`; see native/src/lib.rs.
+const addonPath = path.join(__dirname, '..', '..', 'native', 'zaphnath.node')
+
+const CHUNK_SIZE = 64 * 1024
+const HASH_SLICE = 1024 * 1024
+// Same limit as MAX_OUTPUT in native/src/compress.rs.
+const MAX_OUTPUT = 64 * 1024 * 1024
+const NEWLINE = 0x0a
+
+const gzipAsync = promisify(zlib.gzip)
+const gunzipAsync = promisify(zlib.gunzip)
+
+// Pure-JS versions of every addon export, so the app works without a Rust
+// toolchain. Results must match the addon exactly, and like the addon none of
+// them may hold the main thread for long.
+const fallback = {
+  sha256: async (data) => {
+    const hash = crypto.createHash('sha256')
+    for (let offset = 0; offset < data.length; offset += HASH_SLICE) {
+      hash.update(data.subarray(offset, offset + HASH_SLICE))
+      await new Promise((resolve) => setImmediate(resolve))
+    }
+    return hash.digest('hex')
+  },
+  gzip: (data, level = 6) => gzipAsync(data, { level: Math.min(level, 9) }),
+  gunzip: (data) => gunzipAsync(data, { maxOutputLength: MAX_OUTPUT }),
+  inspectFile: async (file) => {
+    const hash = crypto.createHash('sha256')
+    let bytes = 0
+    let lines = 0
+    let longestLine = 0
+    let current = 0
+    for await (const chunk of fs.createReadStream(file, { highWaterMark: CHUNK_SIZE })) {
+      hash.update(chunk)
+      bytes += chunk.length
+      let start = 0
+      for (let index = chunk.indexOf(NEWLINE); index !== -1; index = chunk.indexOf(NEWLINE, start)) {
+        lines += 1
+        longestLine = Math.max(longestLine, current + index - start)
+        current = 0
+        start = index + 1
+      }
+      current += chunk.length - start
+    }
+    if (current > 0) {
+      lines += 1
+      longestLine = Math.max(longestLine, current)
+    }
+    return { bytes, lines, longestLine, sha256: hash.digest('hex') }
+  },
+}
+
+let addon
+
+// ZAPHNATH_NATIVE=0 forces the fallback, e.g. to compare the two.
+const load = () => {
+  if (process.env.ZAPHNATH_NATIVE === '0') return null
+  try {
+    return require(addonPath)
+  } catch (error) {
+    if (error.code !== 'MODULE_NOT_FOUND') log.warn('Native addon failed to load, using JavaScript fallback', { error })
+    return null
+  }
+}
+
+const implementation = () => {
+  if (addon === undefined) addon = load()
+  return addon || fallback
+}
+
+const backend = () => (implementation() === fallback ? 'javascript' : 'native')
+
+// The renderer never passes paths in; the user picks the file here.
+const chooseAndInspect = async (window) => {
+  const { canceled, filePaths } = await dialog.showOpenDialog(window, {
+    title: i18n.t('dialog.inspectFile.title'),
+    properties: ['openFile'],
+  })
+  if (canceled || filePaths.length === 0) return null
+  const stats = await implementation().inspectFile(filePaths[0])
+  log.info('Inspected file', { file: filePaths[0], backend: backend() })
+  return { name: path.basename(filePaths[0]), ...stats }
+}
+
+// IPC hands over plain Uint8Arrays; the addon only accepts Buffers.
+const toBuffer = (data) => (Buffer.isBuffer(data) ? data : Buffer.from(data.buffer, data.byteOffset, data.byteLength))
+
+module.exports = {
+  backend,
+  chooseAndInspect,
+  fallback,
+  sha256: (data) => implementation().sha256(toBuffer(data)),
+  gzip: (data, level) => implementation().gzip(toBuffer(data), level),
+  gunzip: (data) => implementation().gunzip(toBuffer(data)),
+  inspectFile: (file) => implementation().inspectFile(file),
+}
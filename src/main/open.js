@@ -0,0 +1,76 @@
+const { app } = require('electron')
+const fs = require('fs')
+const path = require('path')
+const deepLink = require('./deep-link')
+const logger = require('./logger')
+const security = require('./security')
+const settings = require('./settings')
+const windows = require('./windows')
+
+const log = logger.createLogger('open')
+
+const URL_PATTERN = /^[a-z][a-z0-9+.-]+:/i
+const MAX_RECENT = 10
+
+let ready = false
+const pending = []
+
+const remember = (file) => {
+  const recent = settings.get('recent.files').filter((entry) => entry !== file)
+  settings.set('recent.files', [file, ...recent].slice(0, MAX_RECENT))
+}
+
+const openFile = (file) => {
+  log.info('Opening file', { file })
+  remember(file)
+  return windows.open('document', { file })
+}
+
+const openUrl = (url) => {
+  log.info('Opening URL', { url })
+  return security.openExternal(url)
+}
+
+const dispatch = (target) => {
+  if (deepLink.isDeepLink(target)) return deepLink.handle(target)
+  if (URL_PATTERN.test(target)) return openUrl(target)
+  if (!fs.existsSync(target)) return log.warn('Ignoring missing path', { target })
+  return openFile(target)
+}
+
+// Every entry point (command line, second instance, macOS open-file and
+// open-url) funnels through here. Targets that arrive before the app is
+// ready are queued.
+const open = (target) => {
+  if (ready) dispatch(target)
+  else pending.push(target)
+}
+
+// Drops the executable (and the app path when running unpackaged) plus any
+// Chromium switches, then resolves file arguments against `workingDirectory`.
+const targets = (argv, workingDirectory) =>
+  argv
+    .slice(process.defaultApp ? 2 : 1)
+    .filter((arg) => arg && !arg.startsWith('-'))
+    .map((arg) => (URL_PATTERN.test(arg) ? arg : path.resolve(workingDirectory, arg)))
+
+const handleArgv = (argv, workingDirectory = process.cwd()) => {
+  for (const target of targets(argv, workingDirectory)) open(target)
+}
+
+const start = () => {
+  ready = true
+  for (const target of pending.splice(0)) dispatch(target)
+}
+
+app.on('open-file', (event, file) => {
+  event.preventDefault()
+  open(file)
+})
+
+app.on('open-url', (event, url) => {
+  event.preventDefault()
+  open(url)
+})
+
+module.exports = { open, handleArgv, targets, start }
@@ -0,0 +1,95 @@
+const { protocol } = require('electron')
+const fs = require('fs')
+const path = require('path')
+const csp = require('./csp')
+
+const SCHEME = 'app'
+const HOST = 'zaphnath'
+
+const root = path.join(__dirname, '..', '..')
+
+// Only these files and directories (relative to the app root) are served;
+// main-process code and node_modules never are.
+const publicPaths = ['index.html', 'styles.css', 'assets', 'src/renderer']
+
+const mimeTypes = {
+  '.html': 'text/html',
+  '.js': 'text/javascript',
+  '.mjs': 'text/javascript',
+  '.css': 'text/css',
+  '.json': 'application/json',
+  '.map': 'application/json',
+  '.svg': 'image/svg+xml',
+  '.png': 'image/png',
+  '.jpg': 'image/jpeg',
+  '.jpeg': 'image/jpeg',
+  '.gif': 'image/gif',
+  '.webp': 'image/webp',
+  '.ico': 'image/x-icon',
+  '.woff': 'font/woff',
+  '.woff2': 'font/woff2',
+  '.ttf': 'font/ttf',
+  '.txt': 'text/plain',
+  '.wasm': 'application/wasm',
+}
+
+// net::ERR_FILE_NOT_FOUND and net::ERR_ACCESS_DENIED
+const NOT_FOUND = -6
+const ACCESS_DENIED = -10
+
+const isAppUrl = (url) => {
+  try {
+    const parsed = new URL(url)
+    return parsed.protocol === `${SCHEME}:` && parsed.host === HOST
+  } catch {
+    return false
+  }
+}
+
+const appUrl = (file = 'index.html', query = {}) => {
+  const url = new URL(`${SCHEME}://${HOST}/${file}`)
+  for (const [key, value] of Object.entries(query)) url.searchParams.set(key, value)
+  return url.href
+}
+
+// Maps a request URL to a file on disk, or null when it falls outside the
+// public paths. Percent-encoded separators and `..` segments are resolved
+// before the check, so they cannot climb out of the app root.
+const resolve = (url) => {
+  if (!isAppUrl(url)) return null
+  let pathname
+  try {
+    pathname = decodeURIComponent(new URL(url).pathname)
+  } catch {
+    return null
+  }
+  if (pathname.includes('\0')) return null
+  const relative = path.relative(root, path.join(root, pathname === '/' ? 'index.html' : pathname))
+  if (relative.startsWith('..') || path.isAbsolute(relative)) return null
+  const normalized = relative.split(path.sep).join('/')
+  const allowed = publicPaths.some((entry) => normalized === entry || normalized.startsWith(`${entry}/`))
+  return allowed ? path.join(root, relative) : null
+}
+
+const registerScheme = () => {
+  protocol.registerSchemesAsPrivileged([
+    { scheme: SCHEME, privileges: { standard: true, secure: true, supportFetchAPI: true, corsEnabled: true } },
+  ])
+}
+
+const install = () => {
+  protocol.registerBufferProtocol(SCHEME, (request, callback) => {
+    const file = resolve(request.url)
+    if (!file) return callback({ error: ACCESS_DENIED })
+    fs.readFile(file, (error, data) => {
+      if (error) return callback({ error: NOT_FOUND })
+      callback({
+        mimeType: mimeTypes[path.extname(file).toLowerCase()] || 'application/octet-stream',
+        data,
+        headers: { 'Content-Security-Policy': csp.policy() },
+      })
+    })
+  })
+}
+
+module.exports = { SCHEME, HOST, isAppUrl, appUrl, resolve, registerScheme, install }
@@ -0,0 +1,113 @@
+const { app, BrowserWindow, dialog } = require('electron')
+const i18n = require('./i18n')
+const logger = require('./logger')
+const windows = require('./windows')
+
+const log = logger.createLogger('recovery')
+
+// A renderer that crashes more than MAX_CRASHES times within CRASH_WINDOW
+// is not reloaded automatically again; the user decides instead.
+const MAX_CRASHES = 3
+const CRASH_WINDOW = 60 * 1000
+
+const crashTimes = new Map()
+// Renderers we killed ourselves from the "not responding" prompt.
+const restarting = new Set()
+
+const describe = (contents) => {
+  const win = BrowserWindow.fromWebContents(contents)
+  const entry = win && windows.get(win.id)
+  return entry ? `${entry.kind}#${entry.id}` : `webContents#${contents.id}`
+}
+
+const recentCrashes = (contents) => {
+  const now = Date.now()
+  const times = [...(crashTimes.get(contents.id) || []), now].filter((time) => now - time < CRASH_WINDOW)
+  crashTimes.set(contents.id, times)
+  return times.length
+}
+
+// The reload keeps the URL, hash route included, and sessionStorage, so the
+// router puts the user back on the same view with its saved state.
+const onRenderProcessGone = async (event, contents, { reason, exitCode }) => {
+  if (reason === 'clean-exit' || contents.isDestroyed()) return
+  if (restarting.delete(contents.id)) {
+    log.info('Reloading unresponsive renderer', { window: describe(contents) })
+    return contents.reload()
+  }
+
+  log.error('Renderer process gone', { window: describe(contents), reason, exitCode })
+  if (recentCrashes(contents) <= MAX_CRASHES) return contents.reload()
+
+  const win = BrowserWindow.fromWebContents(contents)
+  const { response } = await dialog.showMessageBox(win, {
+    type: 'error',
+    buttons: [i18n.t('recovery.reload'), i18n.t('recovery.close')],
+    defaultId: 0,
+    cancelId: 1,
+    message: i18n.t('recovery.crashed.message'),
+    detail: i18n.t('recovery.crashed.detail', { reason }),
+  })
+  if (contents.isDestroyed()) return
+  if (response === 0) {
+    crashTimes.delete(contents.id)
+    contents.reload()
+  } else if (win) {
+    win.close()
+  }
+}
+
+// Offers to wait or reload while a window is hung. The prompt closes by
+// itself if the renderer recovers first.
+const watch = (win) => {
+  let prompt = null
+  const { webContents } = win
+
+  win.on('unresponsive', async () => {
+    if (prompt) return
+    log.warn('Window not responding', { window: describe(webContents) })
+    prompt = new AbortController()
+    const { signal } = prompt
+    const { response } = await dialog.showMessageBox(win, {
+      type: 'warning',
+      buttons: [i18n.t('recovery.wait'), i18n.t('recovery.reload')],
+      defaultId: 0,
+      cancelId: 0,
+      message: i18n.t('recovery.unresponsive.message', { title: win.getTitle() }),
+      detail: i18n.t('recovery.unresponsive.detail'),
+      signal,
+    })
+    prompt = null
+    if (signal.aborted || response !== 1 || win.isDestroyed()) return
+    restarting.add(webContents.id)
+    webContents.forcefullyCrashRenderer()
+  })
+  win.on('responsive', () => {
+    if (!prompt) return
+    log.info('Window responsive again', { window: describe(webContents) })
+    prompt.abort()
+  })
+  win.on('closed', () => crashTimes.delete(webContents.id))
+}
+
+const onUncaughtException = (error, origin) => {
+  log.error('Uncaught exception in main process', { error, origin })
+  if (app.isReady()) dialog.showErrorBox(i18n.t('recovery.error.title'), error && error.stack ? error.stack : String(error))
+  // The main process is in an unknown state now; keeping it alive would only
+  // hide the fault.
+  app.exit(1)
+}
+
+const onUnhandledRejection = (reason) => {
+  log.error('Unhandled promise rejection in main process', { error: reason })
+}
+
+// Call before the app is ready so failures during startup are logged too.
+const start = () => {
+  process.on('uncaughtException', onUncaughtException)
+  process.on('unhandledRejection', onUnhandledRejection)
+  app.on('render-process-gone', onRenderProcessGone)
+  app.on('browser-window-created', (event, win) => watch(win))
+}
+
+module.exports = { start, MAX_CRASHES }
@@ -0,0 +1,79 @@
+const has = (object, key) => Object.prototype.hasOwnProperty.call(object, key)
+
+const describe = (value) => {
+  if (value === null) return 'null'
+  if (Array.isArray(value)) return 'array'
+  if (value instanceof Uint8Array) return 'bytes'
+  return typeof value
+}
+
+const validate = (schema, value, path = 'value') => {
+  if (value === undefined) return schema.optional ? [] : [`${path} is required`]
+  if (schema.enum) {
+    return schema.enum.includes(value) ? [] : [`${path} must be one of ${schema.enum.map((v) => JSON.stringify(v)).join(', ')}`]
+  }
+  if (schema.oneOf) {
+    return schema.oneOf.some((option) => validate(option, value, path).length === 0)
+      ? []
+      : [`${path} does not match any allowed shape`]
+  }
+
+  const actual = describe(value)
+  switch (schema.type) {
+    case 'any':
+      return []
+    case 'string': {
+      if (actual !== 'string') return [`${path} must be a string, got ${actual}`]
+      if (schema.minLength !== undefined && value.length < schema.minLength) return [`${path} must be at least ${schema.minLength} characters`]
+      if (schema.maxLength !== undefined && value.length > schema.maxLength) return [`${path} must be at most ${schema.maxLength} characters`]
+      if (schema.pattern && !new RegExp(schema.pattern).test(value)) return [`${path} must match ${schema.pattern}`]
+      return []
+    }
+    case 'number': {
+      if (actual !== 'number' || Number.isNaN(value)) return [`${path} must be a number, got ${actual}`]
+      if (schema.integer && !Number.isInteger(value)) return [`${path} must be an integer`]
+      if (schema.minimum !== undefined && value < schema.minimum) return [`${path} must be >= ${schema.minimum}`]
+      if (schema.maximum !== undefined && value > schema.maximum) return [`${path} must be <= ${schema.maximum}`]
+      return []
+    }
+    case 'bytes': {
+      if (actual !== 'bytes') return [`${path} must be bytes, got ${actual}`]
+      if (schema.maxLength !== undefined && value.length > schema.maxLength) return [`${path} must be at most ${schema.maxLength} bytes`]
+      return []
+    }
+    case 'boolean':
+    case 'null':
+      return actual === schema.type ? [] : [`${path} must be ${schema.type}, got ${actual}`]
+    case 'array': {
+      if (actual !== 'array') return [`${path} must be an array, got ${actual}`]
+      if (schema.maxItems !== undefined && value.length > schema.maxItems) return [`${path} must have at most ${schema.maxItems} items`]
+      if (!schema.items) return []
+      return value.flatMap((item, index) => validate(schema.items, item, `${path}[${index}]`))
+    }
+    case 'object': {
+      if (actual !== 'object') return [`${path} must be an object, got ${actual}`]
+      const properties = schema.properties || {}
+      const errors = Object.entries(properties).flatMap(([key, property]) => validate(property, value[key], `${path}.${key}`))
+      if (schema.additionalProperties) {
+        for (const key of Object.keys(value)) {
+          if (!has(properties, key)) errors.push(...validate(schema.additionalProperties, value[key], `${path}.${key}`))
+        }
+      } else if (schema.properties) {
+        for (const key of Object.keys(value)) {
+          if (!has(properties, key)) errors.push(`${path}.${key} is not allowed`)
+        }
+      }
+      return errors
+    }
+    default:
+      throw new Error(`Unknown schema type '${schema.type}' at ${path}`)
+  }
+}
+
+const check = (schema, value, path) => {
+  const errors = validate(schema, value, path)
+  if (errors.length > 0) throw new TypeError(errors.join('; '))
+  return value
+}
+
+module.exports = { validate, check }
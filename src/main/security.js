@@ -0,0 +1,78 @@
+const { app, session, shell } = require('electron')
+const logger = require('./logger')
+const { isAppUrl } = require('./protocol')
+const settings = require('./settings')
+
+const log = logger.createLogger('security')
+
+// Hosts the app may hand to the system browser. Anything else a page tries
+// to open is dropped.
+const externalHosts = new Set([
+  'github.com',
+  'www.electronjs.org',
+])
+
+// Permissions that carry no privacy risk and are always granted to app pages.
+// Everything else (camera, microphone, geolocation, notifications, ...) is
+// denied unless listed in the `security.allowedPermissions` setting.
+const safePermissions = new Set(['clipboard-sanitized-write', 'fullscreen'])
+
+const webPreferences = (preferences = {}) => ({
+  ...preferences,
+  contextIsolation: true,
+  sandbox: true,
+  nodeIntegration: false,
+  nodeIntegrationInWorker: false,
+  nodeIntegrationInSubFrames: false,
+  webSecurity: true,
+  allowRunningInsecureContent: false,
+  webviewTag: false,
+  experimentalFeatures: false,
+})
+
+const isAllowedExternal = (url) => {
+  try {
+    const { protocol, hostname } = new URL(url)
+    return protocol === 'https:' && externalHosts.has(hostname)
+  } catch {
+    return false
+  }
+}
+
+const openExternal = (url) => {
+  if (!isAllowedExternal(url)) {
+    log.warn('Blocked opening external URL', { url })
+    return false
+  }
+  shell.openExternal(url)
+  return true
+}
+
+const isPermissionAllowed = (permission, requestingUrl) =>
+  isAppUrl(requestingUrl) &&
+  (safePermissions.has(permission) || settings.get('security.allowedPermissions').includes(permission))
+
+const secureContents = (contents) => {
+  contents.on('will-navigate', (event, url) => {
+    if (isAppUrl(url)) return
+    event.preventDefault()
+    openExternal(url)
+  })
+  contents.setWindowOpenHandler(({ url }) => {
+    openExternal(url)
+    return { action: 'deny' }
+  })
+  contents.on('will-attach-webview', (event) => event.preventDefault())
+}
+
+const install = () => {
+  app.on('web-contents-created', (event, contents) => secureContents(contents))
+
+  session.defaultSession.setPermissionRequestHandler((contents, permission, callback, details) => {
+    callback(isPermissionAllowed(permission, details.requestingUrl))
+  })
+  session.defaultSession.setPermissionCheckHandler((contents, permission, requestingOrigin, details) =>
+    isPermissionAllowed(permission, details.requestingUrl || (contents && contents.getURL())))
+}
+
+module.exports = { webPreferences, isAllowedExternal, openExternal, install }
@@ -0,0 +1,50 @@
+const PATTERN = /^v?(0|[1-9]\d*)\.(0|[1-9]\d*)\.(0|[1-9]\d*)(?:-([0-9A-Za-z-]+(?:\.[0-9A-Za-z-]+)*))?(?:\+([0-9A-Za-z-]+(?:\.[0-9A-Za-z-]+)*))?$/
+
+const parse = (version) => {
+  const match = PATTERN.exec(String(version).trim())
+  if (!match) return null
+  const [, major, minor, patch, prerelease, build] = match
+  return {
+    major: Number(major),
+    minor: Number(minor),
+    patch: Number(patch),
+    prerelease: prerelease ? prerelease.split('.') : [],
+    build: build ? build.split('.') : [],
+  }
+}
+
+const valid = (version) => parse(version) !== null
+
+const compareIdentifiers = (a, b) => {
+  const numericA = /^\d+$/.test(a)
+  const numericB = /^\d+$/.test(b)
+  if (numericA && numericB) return Math.sign(Number(a) - Number(b))
+  if (numericA) return -1
+  if (numericB) return 1
+  return a < b ? -1 : a > b ? 1 : 0
+}
+
+// Orders versions per semver 2.0.0: build metadata is ignored and a
+// prerelease sorts before the release it precedes.
+const compare = (left, right) => {
+  const a = parse(left)
+  const b = parse(right)
+  if (!a || !b) throw new TypeError(`Invalid version: ${a ? right : left}`)
+  for (const key of ['major', 'minor', 'patch']) {
+    if (a[key] !== b[key]) return Math.sign(a[key] - b[key])
+  }
+  if (a.prerelease.length === 0 || b.prerelease.length === 0) {
+    return Math.sign(b.prerelease.length - a.prerelease.length)
+  }
+  for (let index = 0; index < Math.max(a.prerelease.length, b.prerelease.length); index += 1) {
+    if (a.prerelease[index] === undefined) return -1
+    if (b.prerelease[index] === undefined) return 1
+    const order = compareIdentifiers(a.prerelease[index], b.prerelease[index])
+    if (order !== 0) return order
+  }
+  return 0
+}
+
+const isPrerelease = (version) => parse(version).prerelease.length > 0
+
+module.exports = { parse, valid, compare, isPrerelease }
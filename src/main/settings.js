@@ -0,0 +1,110 @@
+const { app } = require('electron')
+const { EventEmitter } = require('events')
+const path = require('path')
+const { readJson, writeJsonAtomic } = require('./json-file')
+const logger = require('./logger')
+const { check, validate } = require('./schema')
+
+// Every setting is declared here with its schema and default. Values are
+// stored flat under their dotted key. Only settings marked `renderer: true`
+// can be changed over IPC; the rest (permissions, update feed, crash
+// endpoint, ...) stay under main-process control.
+const definitions = {
+  'window.restoreState': { schema: { type: 'boolean' }, default: true, renderer: true },
+  'security.allowedPermissions': {
+    schema: {
+      type: 'array',
+      items: { enum: ['media', 'geolocation', 'notifications', 'midi', 'pointerLock', 'openExternal'] },
+    },
+    default: [],
+  },
+  'appearance.theme': { schema: { enum: ['system', 'light', 'dark'] }, default: 'system', renderer: true },
+  'general.locale': { schema: { type: 'string', pattern: '^(system|[a-z]{2,3}(-[A-Za-z]{2,4})?)$' }, default: 'system', renderer: true },
+  'general.runInBackground': { schema: { type: 'boolean' }, default: false, renderer: true },
+  'tray.enabled': { schema: { type: 'boolean' }, default: false, renderer: true },
+  'recent.files': {
+    schema: { type: 'array', maxItems: 10, items: { type: 'string', maxLength: 4096 } },
+    default: [],
+  },
+  'updates.channel': { schema: { enum: ['stable', 'beta'] }, default: 'stable', renderer: true },
+  'updates.feedUrl': { schema: { oneOf: [{ enum: [''] }, { type: 'string', pattern: '^https?://' }] }, default: '' },
+  'updates.autoCheck': { schema: { type: 'boolean' }, default: true, renderer: true },
+  'jobs.concurrency': { schema: { type: 'number', integer: true, minimum: 1, maximum: 8 }, default: 2 },
+  'crashReports.upload': { schema: { type: 'boolean' }, default: false, renderer: true },
+  'crashReports.endpoint': { schema: { oneOf: [{ enum: [''] }, { type: 'string', pattern: '^https?://' }] }, default: '' },
+  keybindings: {
+    schema: { type: 'object', additionalProperties: { type: 'string', maxLength: 64 } },
+    default: {},
+  },
+}
+
+// migrations[n] upgrades stored values from version n - 1 to n. Bump VERSION
+// and add an entry whenever a stored key is renamed or changes shape.
+const VERSION = 1
+const migrations = {}
+
+const log = logger.createLogger('settings')
+
+const settings = new EventEmitter()
+
+let values = null
+
+const file = () => path.join(app.getPath('userData'), 'settings.json')
+
+const defaults = () => Object.fromEntries(Object.entries(definitions).map(([key, { default: value }]) => [key, value]))
+
+const migrate = (stored) => {
+  let { version = 0, values: data = {} } = stored
+  while (version < VERSION) {
+    version += 1
+    if (migrations[version]) data = migrations[version](data)
+  }
+  return data
+}
+
+const load = () => {
+  if (values) return values
+  const stored = migrate(readJson(file(), {}))
+  values = defaults()
+  for (const [key, value] of Object.entries(stored)) {
+    if (!definitions[key]) continue
+    const errors = validate(definitions[key].schema, value, key)
+    if (errors.length > 0) log.warn('Ignoring invalid stored setting', { key, errors })
+    else values[key] = value
+  }
+  return values
+}
+
+const save = () => writeJsonAtomic(file(), { version: VERSION, values: load() })
+
+const definition = (key) => {
+  if (!definitions[key]) throw new Error(`Unknown setting '${key}'`)
+  return definitions[key]
+}
+
+const get = (key) => {
+  if (key === undefined) return { ...load() }
+  definition(key)
+  return load()[key]
+}
+
+const set = (key, value) => {
+  check(definition(key).schema, value, key)
+  const previous = load()[key]
+  if (JSON.stringify(previous) === JSON.stringify(value)) return value
+  values[key] = value
+  save()
+  settings.emit('change', { key, value, previous })
+  return value
+}
+
+const reset = (key) => set(key, definition(key).default)
+
+const setFromRenderer = (key, value) => {
+  if (!definition(key).renderer) throw new Error(`Setting '${key}' cannot be changed from a renderer`)
+  return set(key, value)
+}
+
+Object.assign(settings, { definitions, get, set, setFromRenderer, reset })
+
+module.exports = settings
@@ -0,0 +1,200 @@
+const { app } = require('electron')
+const { spawn } = require('child_process')
+const { EventEmitter } = require('events')
+const fs = require('fs')
+const path = require('path')
+const readline = require('readline')
+const logger = require('./logger')
+
+const log = logger.createLogger('sidecar')
+
+const root = path.join(__dirname, '..', '..')
+const executable = process.platform === 'win32' ? 'zaphnath-sidecar.exe' : 'zaphnath-sidecar'
+
+const DEFAULT_TIMEOUT = 30 * 1000
+const STOP_TIMEOUT = 2000
+const MIN_RESTART_DELAY = 250
+const MAX_RESTART_DELAY = 10 * 1000
+// A process that stays up this long resets the restart backoff.
+const STABLE_AFTER = 30 * 1000
+
+const REQUEST_CANCELLED = -32800
+
+// Emits 'status' ({ state, pid, restarts }) whenever the process starts,
+// exits or is restarted.
+const sidecar = new EventEmitter()
+
+let child = null
+let status = { state: 'stopped', pid: null, restarts: 0 }
+let nextId = 1
+const pending = new Map()
+let stopping = false
+let restartTimer = null
+let crashes = 0
+let startedAt = 0
+
+const setStatus = (patch) => {
+  status = { ...status, ...patch }
+  sidecar.emit('status', status)
+}
+
+// Packaged builds ship the binary as an extra resource; development runs use
+// whatever `cargo build` produced last. ZAPHNATH_SIDECAR overrides both.
+const locate = () => {
+  const candidates = process.env.ZAPHNATH_SIDECAR
+    ? [process.env.ZAPHNATH_SIDECAR]
+    : app.isPackaged
+      ? [path.join(process.resourcesPath, executable)]
+      : ['release', 'debug'].map((profile) => path.join(root, 'target', profile, executable))
+  return candidates.find((candidate) => fs.existsSync(candidate)) || null
+}
+
+class RpcError extends Error {
+  constructor({ code, message }) {
+    super(message)
+    this.name = 'RpcError'
+    this.code = code
+  }
+}
+
+const write = (message) => {
+  if (child) child.stdin.write(`${JSON.stringify({ jsonrpc: '2.0', ...message })}\n`)
+}
+
+const settle = (id, outcome) => {
+  const request = pending.get(id)
+  if (!request) return
+  pending.delete(id)
+  clearTimeout(request.timer)
+  if (request.signal) request.signal.removeEventListener('abort', request.onAbort)
+  if (outcome instanceof Error) request.reject(outcome)
+  else request.resolve(outcome)
+}
+
+const onLine = (line) => {
+  let message
+  try {
+    message = JSON.parse(line)
+  } catch (error) {
+    return log.warn('Ignoring malformed sidecar output', { line: line.slice(0, 200) })
+  }
+  if (!pending.has(message.id)) return
+  settle(message.id, message.error ? new RpcError(message.error) : message.result)
+}
+
+const scheduleRestart = () => {
+  crashes = Date.now() - startedAt > STABLE_AFTER ? 1 : crashes + 1
+  const delay = Math.min(MIN_RESTART_DELAY * 2 ** (crashes - 1), MAX_RESTART_DELAY)
+  setStatus({ state: 'restarting', pid: null, restarts: status.restarts + 1 })
+  log.warn('Restarting sidecar', { delay })
+  restartTimer = setTimeout(start, delay)
+}
+
+const start = () => {
+  clearTimeout(restartTimer)
+  if (child) return status
+  stopping = false
+  const command = locate()
+  if (!command) {
+    log.info('Sidecar binary not found; build it with npm run build:sidecar')
+    setStatus({ state: 'unavailable', pid: null })
+    return status
+  }
+
+  const current = spawn(command, [], { stdio: ['pipe', 'pipe', 'pipe'], windowsHide: true })
+  child = current
+  startedAt = Date.now()
+  readline.createInterface({ input: current.stdout }).on('line', onLine)
+  readline.createInterface({ input: current.stderr }).on('line', (line) => log.info(line))
+  current.stdin.on('error', (error) => log.warn('Sidecar stdin closed', { error }))
+  current.on('error', (error) => {
+    log.error('Sidecar failed to start', { error })
+    // A process that never spawned emits no 'exit', so let go of it here.
+    if (child !== current || current.pid !== undefined) return
+    child = null
+    for (const id of [...pending.keys()]) settle(id, new Error('Sidecar failed to start'))
+    setStatus({ state: stopping ? 'stopped' : 'unavailable', pid: null })
+  })
+  current.on('spawn', () => setStatus({ state: 'running', pid: current.pid }))
+  current.on('exit', (code, signal) => {
+    if (child !== current) return
+    child = null
+    for (const id of [...pending.keys()]) settle(id, new Error('Sidecar exited before responding'))
+    if (stopping) {
+      setStatus({ state: 'stopped', pid: null })
+      return
+    }
+    log.error('Sidecar exited unexpectedly', { code, signal })
+    scheduleRestart()
+  })
+  setStatus({ state: 'starting', pid: null })
+  return status
+}
+
+// Closes stdin, which makes the sidecar cancel its work and exit; kills it
+// if it has not gone within STOP_TIMEOUT.
+const stop = () => new Promise((resolve) => {
+  stopping = true
+  clearTimeout(restartTimer)
+  if (!child) {
+    setStatus({ state: 'stopped', pid: null })
+    return resolve()
+  }
+  const current = child
+  const timer = setTimeout(() => current.kill('SIGKILL'), STOP_TIMEOUT)
+  // 'close' also follows a failed spawn, which never emits 'exit'.
+  current.once('close', () => {
+    clearTimeout(timer)
+    resolve()
+  })
+  current.stdin.end()
+})
+
+const cancel = (id) => write({ method: '$/cancelRequest', params: { id } })
+
+// Sends a request and resolves with its result. Timing out or aborting
+// `signal` rejects straight away and tells the sidecar to stop the work.
+const call = (method, params, { timeout = DEFAULT_TIMEOUT, signal } = {}) => new Promise((resolve, reject) => {
+  if (!child) return reject(new Error(`Sidecar is ${status.state}`))
+  if (signal && signal.aborted) return reject(new RpcError({ code: REQUEST_CANCELLED, message: 'Request cancelled' }))
+
+  const id = nextId++
+  const abandon = (error) => {
+    cancel(id)
+    settle(id, error)
+  }
+  const onAbort = () => abandon(new RpcError({ code: REQUEST_CANCELLED, message: 'Request cancelled' }))
+  const timer = setTimeout(() => abandon(new Error(`Sidecar request '${method}' timed out after ${timeout} ms`)), timeout)
+  pending.set(id, { resolve, reject, timer, signal, onAbort })
+  if (signal) signal.addEventListener('abort', onAbort, { once: true })
+  write({ id, method, ...(params === undefined ? {} : { params }) })
+})
+
+// Renderer calls, keyed by webContents id and the requestId the renderer
+// picked, so it can cancel them. Closing the window cancels the rest.
+const rendererCalls = new Map()
+
+const callFor = async (sender, method, params, { timeout, requestId } = {}) => {
+  const controller = new AbortController()
+  const key = `${sender.id}:${requestId || `#${nextId}`}`
+  if (rendererCalls.has(key)) throw new Error(`Request '${requestId}' is already in flight`)
+  const onDestroyed = () => controller.abort()
+  rendererCalls.set(key, controller)
+  sender.once('destroyed', onDestroyed)
+  try {
+    return await call(method, params, { timeout, signal: controller.signal })
+  } finally {
+    rendererCalls.delete(key)
+    sender.removeListener('destroyed', onDestroyed)
+  }
+}
+
+const cancelFor = (sender, requestId) => {
+  const controller = rendererCalls.get(`${sender.id}:${requestId}`)
+  if (controller) controller.abort()
+  return Boolean(controller)
+}
+
+Object.assign(sidecar, { start, stop, call, callFor, cancelFor, status: () => status, RpcError })
+
+module.exports = sidecar
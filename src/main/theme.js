@@ -0,0 +1,42 @@
+const { nativeTheme } = require('electron')
+const { EventEmitter } = require('events')
+const settings = require('./settings')
+
+// Must match --background in styles.css so windows do not flash before the
+// stylesheet loads.
+const backgrounds = { light: '#ffffff', dark: '#1e1e1e' }
+
+const theme = new EventEmitter()
+
+let last = null
+
+const current = () => ({
+  mode: settings.get('appearance.theme'),
+  resolved: nativeTheme.shouldUseDarkColors ? 'dark' : 'light',
+})
+
+const backgroundColor = () => backgrounds[current().resolved]
+
+const notify = () => {
+  const next = current()
+  if (last && last.mode === next.mode && last.resolved === next.resolved) return
+  last = next
+  theme.emit('change', next)
+}
+
+const apply = () => {
+  nativeTheme.themeSource = settings.get('appearance.theme')
+  notify()
+}
+
+const start = () => {
+  apply()
+  nativeTheme.on('updated', notify)
+  settings.on('change', ({ key }) => {
+    if (key === 'appearance.theme') apply()
+  })
+}
+
+Object.assign(theme, { current, backgroundColor, start })
+
+module.exports = theme
@@ -0,0 +1,81 @@
+const { app, Menu, nativeImage, Tray } = require('electron')
+const path = require('path')
+const i18n = require('./i18n')
+const open = require('./open')
+const settings = require('./settings')
+const windows = require('./windows')
+
+const icon = path.join(__dirname, '..', '..', 'assets', 'icon.png')
+
+let tray = null
+let status = { tooltip: '', badge: 0 }
+
+const isMainVisible = () => {
+  const main = windows.find('main')
+  return Boolean(main && main.win.isVisible())
+}
+
+const toggleMain = () => {
+  const main = windows.find('main')
+  if (main && main.win.isVisible()) main.win.hide()
+  else windows.focusOrOpen('main')
+}
+
+const buildMenu = () => {
+  const recent = settings.get('recent.files')
+  return Menu.buildFromTemplate([
+    { label: i18n.t(isMainVisible() ? 'tray.hide' : 'tray.show'), click: toggleMain },
+    { type: 'separator' },
+    {
+      label: i18n.t('tray.recent'),
+      enabled: recent.length > 0,
+      submenu: recent.map((file) => ({ label: path.basename(file), toolTip: file, click: () => open.open(file) })),
+    },
+    { type: 'separator' },
+    { label: i18n.t('tray.quit'), click: () => app.quit() },
+  ])
+}
+
+const refresh = () => {
+  if (!tray) return
+  const { tooltip, badge } = status
+  const name = i18n.t('app.name')
+  tray.setToolTip([tooltip ? `${name} — ${tooltip}` : name, badge ? `(${badge})` : ''].filter(Boolean).join(' '))
+  if (process.platform === 'darwin') tray.setTitle(badge ? String(badge) : '')
+  tray.setContextMenu(buildMenu())
+}
+
+const create = () => {
+  if (tray) return
+  tray = new Tray(nativeImage.createFromPath(icon).resize({ width: 22, height: 22 }))
+  tray.on('click', toggleMain)
+  refresh()
+}
+
+const destroy = () => {
+  if (!tray) return
+  tray.destroy()
+  tray = null
+}
+
+const setStatus = (next) => {
+  status = { ...status, ...next }
+  app.setBadgeCount(status.badge)
+  refresh()
+}
+
+// Background mode only applies while the tray icon exists; without it there
+// would be no way back into the app.
+const keepsAppRunning = () => Boolean(tray) && settings.get('general.runInBackground')
+
+const start = () => {
+  if (settings.get('tray.enabled')) create()
+  settings.on('change', ({ key, value }) => {
+    if (key === 'tray.enabled') value ? create() : destroy()
+    if (key === 'recent.files') refresh()
+  })
+  windows.on('change', refresh)
+  i18n.on('change', refresh)
+}
+
+module.exports = { start, setStatus, keepsAppRunning }
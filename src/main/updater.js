@@ -0,0 +1,185 @@
+const { app, shell } = require('electron')
+const crypto = require('crypto')
+const { EventEmitter } = require('events')
+const fs = require('fs')
+const path = require('path')
+const http = require('./http')
+const { readJson, writeJsonAtomic } = require('./json-file')
+const logger = require('./logger')
+const { check: checkSchema } = require('./schema')
+const semver = require('./semver')
+const settings = require('./settings')
+
+const log = logger.createLogger('updater')
+
+const FIRST_CHECK_DELAY = 10 * 1000
+const CHECK_INTERVAL = 6 * 60 * 60 * 1000
+
+// Feeds publish one manifest per channel and platform, e.g.
+// <feed>/stable/latest-linux-x64.json. `url` may be relative to the manifest.
+// `signature` must be a base64 Ed25519 signature over "<version>\n<sha256>"
+// made with the key in assets/update-key.pem; without that key nothing is
+// downloaded.
+const manifestSchema = {
+  type: 'object',
+  additionalProperties: { type: 'any' },
+  properties: {
+    version: { type: 'string', maxLength: 64 },
+    url: { type: 'string', maxLength: 2048 },
+    sha256: { type: 'string', pattern: '^[0-9a-f]{64}$' },
+    signature: { type: 'string', optional: true },
+    notes: { type: 'string', optional: true },
+  },
+}
+
+const publicKeyFile = () => process.env.ZAPHNATH_UPDATE_KEY || path.join(__dirname, '..', '..', 'assets', 'update-key.pem')
+
+const updater = new EventEmitter()
+
+let status = { state: 'idle' }
+let running = null
+let timer = null
+
+const directory = () => path.join(app.getPath('userData'), 'updates')
+
+const stagedFile = () => path.join(directory(), 'staged.json')
+
+const setStatus = (next) => {
+  status = next
+  updater.emit('status', status)
+}
+
+const feedUrl = () => process.env.ZAPHNATH_UPDATE_FEED || settings.get('updates.feedUrl')
+
+const manifestUrl = (feed) => {
+  const base = feed.endsWith('/') ? feed : `${feed}/`
+  return new URL(`${settings.get('updates.channel')}/latest-${process.platform}-${process.arch}.json`, base).href
+}
+
+const verifySignature = (manifest) => {
+  if (!fs.existsSync(publicKeyFile())) throw new Error('No update signing key is bundled, refusing to install unverified updates')
+  if (!manifest.signature) throw new Error('Update manifest is not signed')
+  const signed = Buffer.from(`${manifest.version}\n${manifest.sha256}`)
+  const key = crypto.createPublicKey(fs.readFileSync(publicKeyFile()))
+  if (!crypto.verify(null, signed, key, Buffer.from(manifest.signature, 'base64'))) {
+    throw new Error('Update manifest signature is invalid')
+  }
+}
+
+const isNewer = (version) => {
+  if (settings.get('updates.channel') === 'stable' && semver.isPrerelease(version)) return false
+  return semver.compare(version, app.getVersion()) > 0
+}
+
+const staged = () => {
+  const entry = readJson(stagedFile(), null)
+  if (!entry || !fs.existsSync(entry.file) || !isNewer(entry.version)) return null
+  return entry
+}
+
+const stage = async (manifest, url) => {
+  const target = path.join(directory(), manifest.version)
+  const partial = path.join(directory(), 'download.partial')
+  fs.rmSync(target, { recursive: true, force: true })
+  fs.mkdirSync(target, { recursive: true })
+
+  let reported = 0
+  const { sha256 } = await http.download(url, partial, (progress) => {
+    if (progress - reported < 0.01 && progress < 1) return
+    reported = progress
+    setStatus({ state: 'downloading', version: manifest.version, progress })
+  })
+  if (sha256 !== manifest.sha256) {
+    fs.rmSync(partial, { force: true })
+    throw new Error(`Checksum mismatch for ${manifest.version}: expected ${manifest.sha256}, got ${sha256}`)
+  }
+
+  const file = path.join(target, path.basename(new URL(url).pathname) || 'update')
+  fs.renameSync(partial, file)
+  for (const name of fs.readdirSync(directory())) {
+    if (semver.valid(name) && name !== manifest.version) fs.rmSync(path.join(directory(), name), { recursive: true, force: true })
+  }
+  const entry = { version: manifest.version, file, notes: manifest.notes || '' }
+  writeJsonAtomic(stagedFile(), entry)
+  return entry
+}
+
+const run = async () => {
+  const feed = feedUrl()
+  if (!feed) return setStatus({ state: 'disabled' })
+
+  setStatus({ state: 'checking' })
+  const url = manifestUrl(feed)
+  const manifest = checkSchema(manifestSchema, await http.getJson(url), 'update manifest')
+  if (!semver.valid(manifest.version)) throw new Error(`Invalid version in update manifest: ${manifest.version}`)
+  if (!isNewer(manifest.version)) return setStatus({ state: 'up-to-date', version: app.getVersion() })
+
+  const existing = staged()
+  if (existing && existing.version === manifest.version) {
+    return setStatus({ state: 'ready', version: existing.version, notes: existing.notes })
+  }
+
+  verifySignature(manifest)
+  log.info('Downloading update', { version: manifest.version, url })
+  setStatus({ state: 'downloading', version: manifest.version, progress: 0 })
+  const entry = await stage(manifest, new URL(manifest.url, url).href)
+  log.info('Update staged', { version: entry.version, file: entry.file })
+  setStatus({ state: 'ready', version: entry.version, notes: entry.notes })
+}
+
+const check = async () => {
+  if (!running) {
+    running = run()
+      .catch((error) => {
+        log.error('Update check failed', { error })
+        setStatus({ state: 'error', message: error.message })
+      })
+      .finally(() => {
+        running = null
+      })
+  }
+  await running
+  return status
+}
+
+// AppImage builds replace themselves in place and relaunch; other formats
+// hand the staged package to the system installer and quit.
+const install = () => {
+  const entry = staged()
+  if (!entry) throw new Error('No update is ready to install')
+  log.info('Installing update', { version: entry.version })
+  if (process.env.APPIMAGE) {
+    const replacement = `${process.env.APPIMAGE}.update`
+    fs.copyFileSync(entry.file, replacement)
+    fs.chmodSync(replacement, 0o755)
+    fs.renameSync(replacement, process.env.APPIMAGE)
+    fs.rmSync(stagedFile(), { force: true })
+    app.relaunch()
+  } else {
+    shell.openPath(entry.file)
+  }
+  app.quit()
+}
+
+const schedule = () => {
+  clearTimeout(timer)
+  if (!settings.get('updates.autoCheck')) return
+  timer = setTimeout(async () => {
+    await check()
+    timer = setInterval(check, CHECK_INTERVAL)
+  }, FIRST_CHECK_DELAY)
+}
+
+const start = () => {
+  const existing = staged()
+  if (existing) setStatus({ state: 'ready', version: existing.version, notes: existing.notes })
+  schedule()
+  settings.on('change', ({ key }) => {
+    if (key === 'updates.autoCheck') schedule()
+    if (key === 'updates.channel' || key === 'updates.feedUrl') check()
+  })
+}
+
+Object.assign(updater, { status: () => status, check, install, start })
+
+module.exports = updater
@@ -0,0 +1,3 @@
+// Views the renderer can route to. Deep links and main-process navigation
+// are validated against this list.
+module.exports = ['home', 'settings', 'about', 'diagnostics', 'jobs']
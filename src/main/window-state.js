@@ -0,0 +1,73 @@
+const { app, screen } = require('electron')
+const path = require('path')
+const { readJson, writeJsonAtomic } = require('./json-file')
+const settings = require('./settings')
+
+const SAVE_DELAY = 500
+
+let states = null
+
+const file = () => path.join(app.getPath('userData'), 'window-state.json')
+
+const load = () => {
+  if (!states) states = readJson(file(), {})
+  return states
+}
+
+const save = () => writeJsonAtomic(file(), load())
+
+const intersects = (a, b) =>
+  a.x < b.x + b.width && b.x < a.x + a.width && a.y < b.y + b.height && b.y < a.y + a.height
+
+// Returns BrowserWindow options for the saved geometry. When the display the
+// window was last on is gone, or the bounds no longer overlap it, only the
+// size is kept (clamped to the primary display) and the window is centered.
+const restore = (key, defaults) => {
+  const saved = settings.get('window.restoreState') && load()[key]
+  if (!saved) return { width: defaults.width, height: defaults.height }
+
+  const display = screen.getAllDisplays().find(({ id }) => id === saved.displayId)
+  if (display && intersects(saved.bounds, display.workArea)) return { ...saved.bounds }
+
+  const { workArea } = screen.getPrimaryDisplay()
+  return {
+    width: Math.min(saved.bounds.width, workArea.width),
+    height: Math.min(saved.bounds.height, workArea.height),
+    center: true,
+  }
+}
+
+const capture = (key, win) => {
+  const isMaximized = win.isMaximized()
+  const isFullScreen = win.isFullScreen()
+  const bounds = isMaximized || isFullScreen || win.isMinimized() ? win.getNormalBounds() : win.getBounds()
+  load()[key] = { bounds, isMaximized, isFullScreen, displayId: screen.getDisplayMatching(bounds).id }
+}
+
+const track = (key, win) => {
+  const saved = settings.get('window.restoreState') && load()[key]
+  win.once('ready-to-show', () => {
+    if (saved && saved.isMaximized) win.maximize()
+    if (saved && saved.isFullScreen) win.setFullScreen(true)
+  })
+
+  let timer = null
+  const schedule = () => {
+    clearTimeout(timer)
+    timer = setTimeout(() => {
+      if (win.isDestroyed()) return
+      capture(key, win)
+      save()
+    }, SAVE_DELAY)
+  }
+  for (const event of ['move', 'resize', 'maximize', 'unmaximize', 'enter-full-screen', 'leave-full-screen']) {
+    win.on(event, schedule)
+  }
+  win.on('close', () => {
+    clearTimeout(timer)
+    capture(key, win)
+    save()
+  })
+}
+
+module.exports = { restore, track }
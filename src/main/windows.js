@@ -0,0 +1,107 @@
+const { BrowserWindow, screen } = require('electron')
+const { EventEmitter } = require('events')
+const path = require('path')
+const ipc = require('./ipc')
+const { appUrl } = require('./protocol')
+const security = require('./security')
+const theme = require('./theme')
+const windowState = require('./window-state')
+
+const root = path.join(__dirname, '..', '..')
+const CASCADE_OFFSET = 24
+
+const kinds = {
+  main: { singleton: true, persistState: true, width: 800, height: 600 },
+  settings: { singleton: true, persistState: true, width: 640, height: 480, minimizable: false },
+  about: { singleton: true, width: 420, height: 320, resizable: false, minimizable: false, maximizable: false },
+  document: { singleton: false, width: 800, height: 600 },
+}
+
+// Emits 'change' whenever a window opens, closes, shows or hides.
+const manager = new EventEmitter()
+
+const windows = new Map()
+
+const find = (kind) => [...windows.values()].find((entry) => entry.kind === kind)
+
+const list = (kind) => [...windows.values()].filter((entry) => !kind || entry.kind === kind)
+
+const get = (id) => windows.get(id)
+
+const focus = (win) => {
+  if (win.isMinimized()) win.restore()
+  if (!win.isVisible()) win.show()
+  win.focus()
+  return win
+}
+
+// Window state is saved per kind, so only singleton kinds persist it. Other
+// kinds open down and to the right of the newest window of the same kind,
+// wrapping back to the top-left of the work area when they would run off it.
+const cascade = (kind, { width, height }) => {
+  const previous = list(kind).filter((entry) => !entry.win.isDestroyed()).pop()
+  if (!previous) return {}
+  const bounds = previous.win.getBounds()
+  const { workArea } = screen.getDisplayMatching(bounds)
+  const x = bounds.x + CASCADE_OFFSET
+  const y = bounds.y + CASCADE_OFFSET
+  if (x + width > workArea.x + workArea.width || y + height > workArea.y + workArea.height) {
+    return { x: workArea.x, y: workArea.y }
+  }
+  return { x, y }
+}
+
+const open = (kind, params = {}) => {
+  const definition = kinds[kind]
+  if (!definition) throw new Error(`Unknown window kind '${kind}'`)
+
+  if (definition.singleton) {
+    const existing = find(kind)
+    if (existing) return focus(existing.win)
+  }
+
+  const { singleton, persistState, ...options } = definition
+  const win = new BrowserWindow({
+    ...options,
+    ...(persistState ? windowState.restore(kind, options) : {}),
+    ...(singleton ? {} : cascade(kind, options)),
+    show: false,
+    backgroundColor: theme.backgroundColor(),
+    webPreferences: security.webPreferences({
+      preload: path.join(root, 'preload.js'),
+    }),
+  })
+  const { id } = win
+  windows.set(id, { id, kind, win, params })
+  win.on('closed', () => {
+    windows.delete(id)
+    manager.emit('change')
+  })
+  win.on('show', () => manager.emit('change'))
+  win.on('hide', () => manager.emit('change'))
+  if (persistState) windowState.track(kind, win)
+  win.once('ready-to-show', () => win.show())
+  win.loadURL(appUrl('index.html', { window: kind, ...params }))
+  manager.emit('change')
+  return win
+}
+
+const focusOrOpen = (kind, params) => {
+  const existing = find(kind)
+  return existing ? focus(existing.win) : open(kind, params)
+}
+
+// `target` narrows the recipients: a window kind, a list of kinds, or a
+// predicate over the tracked entries. Without one every window gets it.
+const broadcast = (name, payload, target) => {
+  const matches = typeof target === 'function'
+    ? target
+    : (entry) => !target || [].concat(target).includes(entry.kind)
+  for (const entry of windows.values()) {
+    if (matches(entry) && !entry.win.isDestroyed()) ipc.send(entry.win.webContents, name, payload)
+  }
+}
+
+Object.assign(manager, { kinds, open, focusOrOpen, focus, find, get, list, broadcast })
+
+module.exports = manager
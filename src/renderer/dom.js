@@ -0,0 +1,15 @@
+// Small element builder. `on<Event>` attributes become listeners, `className`
+// and `textContent`-style properties are assigned, everything else is set as
+// an attribute.
+export const h = (tag, attributes = {}, ...children) => {
+  const element = document.createElement(tag)
+  for (const [key, value] of Object.entries(attributes)) {
+    if (value === undefined || value === null || value === false) continue
+    if (key.startsWith('on')) element.addEventListener(key.slice(2).toLowerCase(), value)
+    else if (key in element && typeof value !== 'string') element[key] = value
+    else if (key === 'className' || key === 'textContent') element[key] = value
+    else element.setAttribute(key, value === true ? '' : value)
+  }
+  element.append(...children.flat().filter((child) => child !== null && child !== undefined && child !== false))
+  return element
+}
@@ -0,0 +1,17 @@
+// Forwards uncaught errors and unhandled rejections to the main log. Errors
+// lose their stack crossing the bridge, so send the parts we need as plain
+// fields. Imported first so it also covers failures while starting up.
+const plain = (error) => (error instanceof Error
+  ? { name: error.name, message: error.message, stack: error.stack }
+  : { message: String(error) })
+
+window.addEventListener('error', (event) => {
+  window.log.error(`Uncaught error: ${event.message}`, {
+    error: plain(event.error || event.message),
+    source: `${event.filename}:${event.lineno}:${event.colno}`,
+  })
+})
+
+window.addEventListener('unhandledrejection', (event) => {
+  window.log.error('Unhandled promise rejection', { error: plain(event.reason) })
+})
@@ -0,0 +1,48 @@
+let catalog = { locale: 'en', dir: 'ltr', messages: {}, available: [] }
+const listeners = new Set()
+
+// Same rules as format() in src/main/i18n.js: {name} placeholders, and
+// plural messages keyed by Intl.PluralRules category.
+const format = (message, params = {}) => {
+  if (message === undefined) return undefined
+  if (typeof message === 'object') {
+    message = message[new Intl.PluralRules(catalog.locale).select(params.count)] || message.other
+  }
+  return message.replace(/\{(\w+)\}/g, (match, name) => {
+    if (!(name in params)) return match
+    const value = params[name]
+    return typeof value === 'number' ? new Intl.NumberFormat(catalog.locale).format(value) : String(value)
+  })
+}
+
+export const t = (key, params) => format(catalog.messages[key], params) || key
+
+export const locales = () => catalog.available
+
+// Static markup opts in with data-i18n (text) or data-i18n-placeholder
+// (placeholder and accessible name).
+const translateDocument = () => {
+  document.documentElement.lang = catalog.locale
+  document.documentElement.dir = catalog.dir
+  for (const element of document.querySelectorAll('[data-i18n]')) element.textContent = t(element.dataset.i18n)
+  for (const element of document.querySelectorAll('[data-i18n-placeholder]')) {
+    element.placeholder = t(element.dataset.i18nPlaceholder)
+    element.setAttribute('aria-label', element.placeholder)
+  }
+}
+
+const apply = (next) => {
+  catalog = next
+  translateDocument()
+  for (const listener of listeners) listener(catalog.locale)
+}
+
+export const onLocaleChange = (listener) => {
+  listeners.add(listener)
+  return () => listeners.delete(listener)
+}
+
+export const load = async () => {
+  apply(await window.i18n.getCatalog())
+  window.i18n.onChange(apply)
+}
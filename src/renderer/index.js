@@ -0,0 +1,39 @@
+import './errors.js'
+import { load, onLocaleChange } from './i18n.js'
+import { openPalette } from './palette.js'
+import { navigate, refresh, register, start } from './router.js'
+import about from './views/about.js'
+import diagnostics from './views/diagnostics.js'
+import home from './views/home.js'
+import jobs from './views/jobs.js'
+import settings from './views/settings.js'
+
+register('home', home)
+register('settings', settings)
+register('about', about)
+register('diagnostics', diagnostics)
+register('jobs', jobs)
+
+const applyTheme = ({ resolved }) => {
+  document.documentElement.dataset.theme = resolved
+}
+
+window.theme.get().then(applyTheme)
+window.theme.onChange(applyTheme)
+
+window.app.onNavigate(({ view }) => navigate(view))
+
+window.commands.onInvoke(({ id }) => {
+  if (id === 'view.commandPalette') openPalette()
+})
+
+await load()
+onLocaleChange(refresh)
+start(document.getElementById('view'))
+
+const ping = async () => {
+  const response = await window.versions.ping()
+  window.log.info('ping', { response })
+}
+
+ping()
@@ -0,0 +1,57 @@
+import { h } from './dom.js'
+
+const palette = document.getElementById('palette')
+const input = palette.querySelector('input')
+const list = palette.querySelector('ul')
+
+let commands = []
+let selected = 0
+
+const close = () => {
+  palette.hidden = true
+}
+
+const run = (id) => {
+  close()
+  window.commands.execute(id)
+}
+
+const render = () => {
+  const query = input.value.trim().toLowerCase()
+  const matches = commands.filter(({ label }) => label.toLowerCase().includes(query))
+  selected = Math.min(selected, Math.max(matches.length - 1, 0))
+  list.replaceChildren(...matches.map((command, index) => h('li',
+    { className: index === selected ? 'selected' : '', onClick: () => run(command.id) },
+    h('span', { textContent: command.label }),
+    h('kbd', { textContent: command.accelerator || '' }),
+  )))
+  return matches
+}
+
+export const openPalette = async () => {
+  commands = (await window.commands.list()).filter(({ id }) => id !== 'view.commandPalette')
+  input.value = ''
+  selected = 0
+  palette.hidden = false
+  render()
+  input.focus()
+}
+
+input.addEventListener('input', () => {
+  selected = 0
+  render()
+})
+
+input.addEventListener('keydown', (event) => {
+  const matches = render()
+  if (event.key === 'Escape') close()
+  else if (event.key === 'ArrowDown') selected = Math.min(selected + 1, matches.length - 1)
+  else if (event.key === 'ArrowUp') selected = Math.max(selected - 1, 0)
+  else if (event.key === 'Enter' && matches[selected]) return run(matches[selected].id)
+  else return
+  event.preventDefault()
+  render()
+})
+
+input.addEventListener('blur', close)
+list.addEventListener('mousedown', (event) => event.preventDefault())
@@ -0,0 +1,108 @@
+import { t } from './i18n.js'
+
+const STORAGE_PREFIX = 'view-state:'
+
+const routes = new Map()
+const params = new URLSearchParams(location.search)
+
+let outlet = null
+let current = null
+let listeners = []
+
+export const register = (name, view) => {
+  routes.set(name, view)
+}
+
+// View state lives in sessionStorage, which is kept across reloads of the
+// same window (including the recovery reload after a renderer crash).
+export const loadState = (name) => {
+  try {
+    return JSON.parse(sessionStorage.getItem(`${STORAGE_PREFIX}${name}`)) || {}
+  } catch {
+    return {}
+  }
+}
+
+const saveState = (name, state) => {
+  sessionStorage.setItem(`${STORAGE_PREFIX}${name}`, JSON.stringify(state))
+}
+
+const fromHash = () => {
+  const name = location.hash.replace(/^#\/?/, '')
+  return routes.has(name) ? name : null
+}
+
+const defaultRoute = () => {
+  const requested = params.get('view')
+  if (routes.has(requested)) return requested
+  const kind = params.get('window')
+  return routes.has(kind) ? kind : 'home'
+}
+
+export const currentRoute = () => current && current.name
+
+export const onRouteChange = (listener) => {
+  listeners.push(listener)
+  return () => {
+    listeners = listeners.filter((candidate) => candidate !== listener)
+  }
+}
+
+const leave = () => {
+  if (!current) return
+  saveState(current.name, { ...current.state, scrollTop: outlet.scrollTop })
+  if (typeof current.cleanup === 'function') current.cleanup()
+}
+
+const render = async () => {
+  const name = fromHash() || defaultRoute()
+  if (current && current.name === name) return
+  leave()
+
+  const view = routes.get(name)
+  const state = loadState(name)
+  const { scrollTop = 0, ...viewState } = state
+  current = { name, state: viewState, cleanup: null }
+  const entry = current
+
+  // Each render gets its own container, so a view that finishes rendering
+  // after the user has moved on only touches a detached element.
+  const container = document.createElement('div')
+  outlet.replaceChildren(container)
+  document.title = `${t(view.title)} — ${t('app.name')}`
+  for (const link of document.querySelectorAll('[data-route]')) {
+    link.classList.toggle('active', link.dataset.route === name)
+  }
+  const setState = (patch) => {
+    entry.state = { ...entry.state, ...patch }
+    saveState(name, entry.state)
+  }
+  const cleanup = await view.render(container, { state: viewState, setState, params })
+  if (current !== entry) {
+    if (typeof cleanup === 'function') cleanup()
+    return
+  }
+  entry.cleanup = cleanup
+  outlet.scrollTop = scrollTop
+  for (const listener of listeners) listener(name)
+}
+
+// Renders the current view again, e.g. after the locale changes.
+export const refresh = () => {
+  leave()
+  current = null
+  return render()
+}
+
+export const navigate = (name) => {
+  if (!routes.has(name)) throw new Error(`Unknown view '${name}'`)
+  location.hash = `#/${name}`
+}
+
+export const start = (element) => {
+  outlet = element
+  window.addEventListener('hashchange', render)
+  window.addEventListener('beforeunload', leave)
+  if (!fromHash()) history.replaceState(null, '', `#/${defaultRoute()}`)
+  return render()
+}
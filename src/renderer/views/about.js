@@ -0,0 +1,44 @@
+import { h } from '../dom.js'
+import { t } from '../i18n.js'
+
+const describe = ({ state, version, progress, message }) => {
+  switch (state) {
+    case 'disabled': return t('update.disabled')
+    case 'checking': return t('update.checking')
+    case 'up-to-date': return t('update.upToDate')
+    case 'downloading': return t('update.downloading', { version, percent: Math.round((progress || 0) * 100) })
+    case 'ready': return t('update.ready', { version })
+    case 'error': return t('update.error', { message })
+    default: return ''
+  }
+}
+
+export default {
+  title: 'about.title',
+  render: async (root) => {
+    const { versions } = window
+    const status = h('p', { className: 'muted' })
+    const check = h('button', { type: 'button', textContent: t('about.check'), onClick: () => window.updater.check() })
+    const install = h('button', { type: 'button', textContent: t('about.install'), hidden: true, onClick: () => window.updater.install() })
+    const update = (next) => {
+      status.textContent = describe(next)
+      check.disabled = next.state === 'checking' || next.state === 'downloading'
+      install.hidden = next.state !== 'ready'
+    }
+
+    const { app } = await window.diagnostics.snapshot()
+    root.append(
+      h('h1', { textContent: t('app.name') }),
+      h('dl', {},
+        h('dt', { textContent: t('about.version') }), h('dd', { textContent: `${app.version} (${app.commit})` }),
+        h('dt', { textContent: 'Electron' }), h('dd', { textContent: versions.electron() }),
+        h('dt', { textContent: 'Chrome' }), h('dd', { textContent: versions.chrome() }),
+        h('dt', { textContent: 'Node.js' }), h('dd', { textContent: versions.node() }),
+      ),
+      h('div', { className: 'actions' }, check, install),
+      status,
+    )
+    update(await window.updater.getStatus())
+    return window.updater.onStatus(update)
+  },
+}
@@ -0,0 +1,133 @@
+import { h } from '../dom.js'
+import { t } from '../i18n.js'
+
+const REFRESH_INTERVAL = 2000
+
+const megabytes = (bytes) => `${(bytes / 1024 / 1024).toFixed(1)} MB`
+
+const table = (rows) => h('dl', {}, rows.flatMap(([label, value]) => [
+  h('dt', { textContent: label }),
+  h('dd', { textContent: String(value) }),
+]))
+
+const crashList = (crashes, upload) => crashes.length === 0
+  ? h('p', { className: 'muted', textContent: t('diagnostics.noCrashes') })
+  : h('table', { className: 'metrics' },
+    h('thead', {}, h('tr', {}, ['crashProcess', 'version', 'crashWindow'].map((key) => h('th', { textContent: t(`diagnostics.${key}`) })), h('th'))),
+    h('tbody', {}, crashes.map((crash) => h('tr', { title: `${crash.createdAt}\n${crash.lastIpc.join(', ')}` },
+      h('td', { textContent: crash.process }),
+      h('td', { textContent: crash.appVersion }),
+      h('td', { textContent: crash.activeWindow || '' }),
+      h('td', {}, crash.uploadedAt
+        ? t('diagnostics.uploaded', { id: crash.remoteId || crash.id })
+        : h('button', { type: 'button', textContent: t('diagnostics.upload'), onClick: () => upload(crash.id) })),
+    ))),
+  )
+
+const sections = (data, crashes, upload) => [
+  h('h2', { textContent: t('diagnostics.application') }),
+  table([
+    [t('diagnostics.version'), data.app.version],
+    [t('diagnostics.commit'), data.app.commit],
+    [t('diagnostics.build'), data.app.packaged ? data.app.builtAt || t('diagnostics.packaged') : t('diagnostics.development')],
+    [t('diagnostics.locale'), data.app.locale],
+    [t('diagnostics.native'), t(`diagnostics.native.${data.app.native}`)],
+    [t('diagnostics.sidecar'), t(`diagnostics.sidecar.${data.app.sidecar}`)],
+    ...Object.entries(data.versions),
+  ]),
+  h('h2', { textContent: t('diagnostics.system') }),
+  table([
+    [t('diagnostics.platform'), `${data.os.platform} ${data.os.release} (${data.os.arch})`],
+    [t('diagnostics.cpu'), `${data.os.cpus} × ${data.os.cpuModel}`],
+    [t('diagnostics.memory'), t('diagnostics.memoryFree', { free: megabytes(data.os.freeMemory), total: megabytes(data.os.totalMemory) })],
+  ]),
+  h('h2', { textContent: t('diagnostics.processes', { count: data.metrics.length }) }),
+  h('table', { className: 'metrics' },
+    h('thead', {}, h('tr', {}, ['pid', 'type', 'cpu', 'memory'].map((key) => h('th', { textContent: t(`diagnostics.${key}`) })))),
+    h('tbody', {}, data.metrics.map(({ pid, type, name, cpu, memory }) => h('tr', {},
+      h('td', { textContent: String(pid) }),
+      h('td', { textContent: name ? `${type} (${name})` : type }),
+      h('td', { textContent: `${cpu.toFixed(1)}%` }),
+      h('td', { textContent: megabytes(memory) }),
+    ))),
+  ),
+  h('h2', { textContent: t('diagnostics.crashes') }),
+  crashList(crashes, upload),
+  h('h2', { textContent: t('diagnostics.gpu') }),
+  table(Object.entries(data.gpu)),
+  h('h2', { textContent: t('diagnostics.paths') }),
+  table(Object.entries(data.paths)),
+  h('h2', { textContent: t('diagnostics.settings') }),
+  h('pre', { textContent: JSON.stringify(data.settings, null, 2) }),
+]
+
+export default {
+  title: 'diagnostics.title',
+  render: (root, { state, setState }) => {
+    const content = h('div')
+    const result = h('output', { className: 'muted' })
+    let timer = null
+
+    const upload = async (id) => {
+      try {
+        const { remoteId } = await window.crashes.upload(id)
+        result.textContent = t('diagnostics.uploaded', { id: remoteId || id })
+      } catch (error) {
+        result.textContent = t('diagnostics.uploadFailed', { message: error.message })
+      }
+      refresh()
+    }
+    const refresh = async () => {
+      const [data, crashes] = await Promise.all([window.diagnostics.snapshot(), window.crashes.list()])
+      content.replaceChildren(...sections(data, crashes, upload))
+    }
+    const setAutoRefresh = (enabled) => {
+      clearInterval(timer)
+      if (enabled) timer = setInterval(refresh, REFRESH_INTERVAL)
+      setState({ autoRefresh: enabled })
+    }
+    const ping = async () => {
+      const started = performance.now()
+      const response = await window.versions.ping()
+      result.textContent = t('diagnostics.pingResult', { response, ms: Math.round(performance.now() - started) })
+    }
+    const pingSidecar = async () => {
+      const started = performance.now()
+      try {
+        const response = await window.sidecar.call('ping', undefined, { timeout: 5000 })
+        result.textContent = t('diagnostics.pingResult', { response, ms: Math.round(performance.now() - started) })
+      } catch (error) {
+        result.textContent = error.message
+      }
+    }
+    const inspect = async () => {
+      const stats = await window.native.inspectFile()
+      if (stats) result.textContent = t('diagnostics.inspectResult', stats)
+    }
+    const copy = async () => {
+      await window.diagnostics.copyReport()
+      result.textContent = t('diagnostics.copied')
+    }
+
+    root.append(
+      h('h1', { textContent: t('diagnostics.title') }),
+      h('div', { className: 'actions' },
+        h('button', { type: 'button', textContent: t('diagnostics.refresh'), onClick: refresh }),
+        h('label', {},
+          h('input', { type: 'checkbox', checked: state.autoRefresh, onChange: (event) => setAutoRefresh(event.target.checked) }),
+          ` ${t('diagnostics.autoRefresh')}`),
+        h('button', { type: 'button', textContent: t('diagnostics.copy'), onClick: copy }),
+        h('button', { type: 'button', textContent: t('diagnostics.ping'), onClick: ping }),
+        h('button', { type: 'button', textContent: t('diagnostics.pingSidecar'), onClick: pingSidecar }),
+        h('button', { type: 'button', textContent: t('diagnostics.inspectFile'), onClick: inspect }),
+        h('button', { type: 'button', textContent: t('diagnostics.openLogs'), onClick: () => window.commands.execute('help.openLogs') }),
+        h('button', { type: 'button', textContent: t('diagnostics.export'), onClick: () => window.commands.execute('help.exportDiagnostics') }),
+      ),
+      result,
+      content,
+    )
+    setAutoRefresh(Boolean(state.autoRefresh))
+    refresh()
+    return () => clearInterval(timer)
+  },
+}
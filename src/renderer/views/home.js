@@ -0,0 +1,19 @@
+import { h } from '../dom.js'
+import { t } from '../i18n.js'
+
+export default {
+  title: 'home.title',
+  render: (root, { params }) => {
+    const { versions } = window
+    const file = params.get('file')
+    root.append(
+      h('h1', { textContent: t('home.heading') }),
+      h('p', { textContent: '👋' }),
+      h('p', {
+        id: 'info',
+        textContent: t('home.info', { chrome: versions.chrome(), node: versions.node(), electron: versions.electron() }),
+      }),
+      file && h('p', { className: 'muted', textContent: t('home.opened', { file }) }),
+    )
+  },
+}
@@ -0,0 +1,63 @@
+import { h } from '../dom.js'
+import { t } from '../i18n.js'
+
+const DEFAULT_LIMIT = 50000000
+const MAX_LIMIT = 1e9
+
+const describe = (job) => {
+  switch (job.state) {
+    case 'running': return job.partial ? t(`jobs.partial.${job.task}`, job.partial) : t('jobs.state.running')
+    case 'completed': return t(`jobs.result.${job.task}`, job.result)
+    case 'failed': return t('jobs.failed', { message: job.error })
+    default: return t(`jobs.state.${job.state}`)
+  }
+}
+
+const row = (job) => h('tr', {},
+  h('td', { textContent: `#${job.id}` }),
+  h('td', { textContent: t(`jobs.task.${job.task}`) }),
+  h('td', {}, h('progress', { max: 1, value: job.progress })),
+  h('td', { textContent: describe(job) }),
+  h('td', {}, ['queued', 'running'].includes(job.state)
+    && h('button', { type: 'button', textContent: t('jobs.cancel'), onClick: () => window.jobs.cancel(job.id) })),
+)
+
+export default {
+  title: 'jobs.title',
+  render: async (root, { state, setState }) => {
+    const rows = new Map()
+    const body = h('tbody')
+    const empty = h('p', { className: 'muted', textContent: t('jobs.empty') })
+    const show = (job) => {
+      const next = row(job)
+      if (rows.has(job.id)) rows.get(job.id).replaceWith(next)
+      else body.prepend(next)
+      rows.set(job.id, next)
+      empty.hidden = true
+    }
+
+    const limit = h('input', {
+      type: 'number',
+      min: 2,
+      max: MAX_LIMIT,
+      value: state.limit || DEFAULT_LIMIT,
+      onChange: (event) => setState({ limit: Number(event.target.value) }),
+    })
+    const start = () => {
+      const value = Math.min(Math.max(Math.floor(Number(limit.value)) || 0, 0), MAX_LIMIT)
+      window.jobs.start('countPrimes', { limit: value })
+    }
+
+    root.append(
+      h('h1', { textContent: t('jobs.title') }),
+      h('div', { className: 'actions' },
+        h('label', {}, `${t('jobs.limit')} `, limit),
+        h('button', { type: 'button', textContent: t('jobs.start'), onClick: start }),
+      ),
+      empty,
+      h('table', { className: 'metrics jobs' }, body),
+    )
+    for (const job of await window.jobs.list()) show(job)
+    return window.jobs.onUpdate(show)
+  },
+}
@@ -0,0 +1,96 @@
+import { h } from '../dom.js'
+import { locales, t } from '../i18n.js'
+
+const fields = () => [
+  {
+    key: 'appearance.theme',
+    label: 'settings.theme',
+    type: 'select',
+    options: ['system', 'light', 'dark'].map((mode) => [mode, t(`settings.theme.${mode}`)]),
+  },
+  {
+    key: 'general.locale',
+    label: 'settings.language',
+    type: 'select',
+    options: [['system', t('settings.language.system')], ...locales().map(({ code, name }) => [code, name])],
+  },
+  { key: 'window.restoreState', label: 'settings.restoreState', type: 'checkbox' },
+  { key: 'tray.enabled', label: 'settings.tray', type: 'checkbox' },
+  { key: 'general.runInBackground', label: 'settings.runInBackground', type: 'checkbox' },
+  { key: 'updates.autoCheck', label: 'settings.autoCheck', type: 'checkbox' },
+  {
+    key: 'updates.channel',
+    label: 'settings.channel',
+    type: 'select',
+    options: ['stable', 'beta'].map((channel) => [channel, t(`settings.channel.${channel}`)]),
+  },
+  { key: 'crashReports.upload', label: 'settings.crashUpload', type: 'checkbox' },
+]
+
+const control = (field, value) => {
+  const onChange = (event) => {
+    const { target } = event
+    window.settings.set(field.key, field.type === 'checkbox' ? target.checked : target.value)
+  }
+  if (field.type === 'checkbox') {
+    return h('input', { type: 'checkbox', name: field.key, checked: value, onChange })
+  }
+  const select = h('select', { name: field.key, onChange },
+    field.options.map(([option, label]) => h('option', { value: option, textContent: label })))
+  select.value = value
+  return select
+}
+
+const apply = (element, value) => {
+  if (element.type === 'checkbox') element.checked = value
+  else element.value = value
+}
+
+// ipcRenderer.invoke prefixes handler errors with the channel name.
+const reason = (error) => error.message.replace(/^Error invoking remote method '[^']+': (\w*Error: )?/, '')
+
+const shortcut = ({ id, label, accelerator }) => {
+  const input = h('input', { type: 'text', id: `shortcut-${id}`, name: `shortcut:${id}`, value: accelerator || '', spellcheck: 'false' })
+  const save = async (value) => {
+    try {
+      input.value = (await window.commands.setAccelerator(id, value)) || ''
+      input.setCustomValidity('')
+    } catch (error) {
+      input.setCustomValidity(reason(error))
+      input.reportValidity()
+    }
+  }
+  input.addEventListener('change', () => save(input.value.trim()))
+  return h('div', { className: 'field shortcut' },
+    h('label', { textContent: label, for: `shortcut-${id}` }),
+    input,
+    h('button', { type: 'button', textContent: t('settings.shortcuts.reset'), onClick: () => save(null) }),
+  )
+}
+
+export default {
+  title: 'settings.title',
+  render: async (root) => {
+    const [values, commands] = await Promise.all([window.settings.get(), window.commands.list()])
+    const form = h('form', { className: 'settings', onSubmit: (event) => event.preventDefault() },
+      fields().map((field) => h('label', { className: `field ${field.type}` },
+        control(field, values[field.key]),
+        h('span', { textContent: t(field.label) }),
+      )))
+    const shortcuts = h('form', { className: 'settings', onSubmit: (event) => event.preventDefault() },
+      h('h2', { textContent: t('settings.shortcuts') }),
+      h('p', { className: 'muted', textContent: t('settings.shortcuts.hint') }),
+      commands.map(shortcut))
+    root.append(h('h1', { textContent: t('settings.title') }), form, shortcuts)
+
+    return window.settings.onChange(async ({ key, value }) => {
+      const element = form.elements.namedItem(key)
+      if (element) apply(element, value)
+      if (key !== 'keybindings') return
+      for (const command of await window.commands.list()) {
+        const input = shortcuts.elements.namedItem(`shortcut:${command.id}`)
+        if (input && document.activeElement !== input) input.value = command.accelerator || ''
+      }
+    })
+  },
+}
@@ -0,0 +1,48 @@
+// Entry point for job workers, started by src/main/jobs.js with
+// utilityProcess.fork. Runs one job at a time and talks to the main process
+// over process.parentPort.
+const tasks = require('./tasks')
+
+const PROGRESS_INTERVAL = 100
+
+const port = process.parentPort
+const post = (message) => port.postMessage(message)
+
+let current = null
+
+const serializeError = (error) => ({
+  name: error && error.name ? error.name : 'Error',
+  message: error && error.message ? error.message : String(error),
+  stack: error && error.stack ? error.stack : null,
+})
+
+const run = async ({ jobId, task, params }) => {
+  const job = { jobId, cancelled: false }
+  current = job
+  let reportedAt = 0
+  const context = {
+    cancelled: () => job.cancelled,
+    // Throttled so a tight loop cannot flood the main process; the final
+    // update always goes through.
+    progress: (fraction, partial) => {
+      const now = Date.now()
+      if (fraction < 1 && now - reportedAt < PROGRESS_INTERVAL) return
+      reportedAt = now
+      post({ type: 'progress', jobId, progress: Math.min(Math.max(fraction, 0), 1), partial })
+    },
+  }
+  try {
+    if (!Object.prototype.hasOwnProperty.call(tasks, task)) throw new Error(`Unknown task '${task}'`)
+    const result = await tasks[task](params, context)
+    post(job.cancelled ? { type: 'cancelled', jobId } : { type: 'done', jobId, result })
+  } catch (error) {
+    post(job.cancelled ? { type: 'cancelled', jobId } : { type: 'failed', jobId, error: serializeError(error) })
+  } finally {
+    current = null
+  }
+}
+
+port.on('message', ({ data }) => {
+  if (data.type === 'run') run(data)
+  else if (data.type === 'cancel' && current && current.jobId === data.jobId) current.cancelled = true
+})
@@ -0,0 +1,51 @@
+// Tasks the job worker can run. Each receives its params and a context with
+// `progress(fraction, partial)` for reporting and `cancelled()` to poll;
+// a task that sees cancellation should return promptly.
+
+const SEGMENT_SIZE = 1 << 16
+
+// Lets cancel messages in between chunks of synchronous work.
+const yieldToEvents = () => new Promise((resolve) => setImmediate(resolve))
+
+// Segmented sieve, reporting the running count after every segment.
+const countPrimes = async ({ limit }, { progress, cancelled }) => {
+  if (limit < 2) return { count: 0 }
+  const root = Math.floor(Math.sqrt(limit)) + 1
+  const composite = new Uint8Array(root + 1)
+  const base = []
+  for (let n = 2; n <= root; n++) {
+    if (composite[n]) continue
+    base.push(n)
+    for (let multiple = n * n; multiple <= root; multiple += n) composite[multiple] = 1
+  }
+
+  let count = 0
+  const segment = new Uint8Array(SEGMENT_SIZE)
+  for (let low = 2; low <= limit; low += SEGMENT_SIZE) {
+    if (cancelled()) return null
+    const high = Math.min(low + SEGMENT_SIZE - 1, limit)
+    segment.fill(1)
+    for (const prime of base) {
+      if (prime * prime > high) break
+      for (let multiple = Math.max(prime * prime, Math.ceil(low / prime) * prime); multiple <= high; multiple += prime) {
+        segment[multiple - low] = 0
+      }
+    }
+    for (let index = 0; index <= high - low; index++) count += segment[index]
+    progress((high - 1) / (limit - 1), { upTo: high, count })
+    await yieldToEvents()
+  }
+  return { count }
+}
+
+// Waits in `steps` equal slices; handy for exercising the queue.
+const wait = async ({ ms, steps = 10 }, { progress, cancelled }) => {
+  for (let step = 1; step <= steps; step++) {
+    await new Promise((resolve) => setTimeout(resolve, ms / steps))
+    if (cancelled()) return null
+    progress(step / steps, { step })
+  }
+  return { steps }
+}
+
+module.exports = { countPrimes, wait }
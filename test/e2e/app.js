@@ -0,0 +1,29 @@
+const { _electron: electron } = require('@playwright/test')
+const fs = require('fs')
+const os = require('os')
+const path = require('path')
+
+const root = path.join(__dirname, '..', '..')
+
+// Launches the real app against a throwaway userData directory so runs never
+// share settings, window state or the single-instance lock.
+const launch = async ({ args = [], env = {} } = {}) => {
+  const userData = fs.mkdtempSync(path.join(os.tmpdir(), 'zaphnath-e2e-'))
+  const app = await electron.launch({
+    args: [root, ...args],
+    env: { ...process.env, ZAPHNATH_USER_DATA: userData, ...env },
+  })
+  const window = await app.firstWindow()
+  await window.waitForSelector('#view > div > *')
+  return {
+    app,
+    window,
+    userData,
+    close: async () => {
+      await app.close()
+      fs.rmSync(userData, { recursive: true, force: true })
+    },
+  }
+}
+
+module.exports = { launch }
@@ -0,0 +1,52 @@
+const { expect, test } = require('@playwright/test')
+const { launch } = require('./app')
+
+let session
+
+test.beforeEach(async () => {
+  session = await launch()
+})
+
+test.afterEach(async () => {
+  await session.close()
+})
+
+test('shows the runtime versions on the home view', async () => {
+  const versions = await session.app.evaluate(() => process.versions)
+  await expect(session.window.locator('#info')).toContainText(`Electron (v${versions.electron})`)
+  await expect(session.window.locator('#info')).toContainText(`Chrome (v${versions.chrome})`)
+})
+
+test('answers ping over the bridge', async () => {
+  expect(await session.window.evaluate(() => window.versions.ping())).toBe('pong')
+})
+
+test('rejects invalid payloads before they reach a handler', async () => {
+  const error = await session.window.evaluate(() => window.settings.set('appearance.theme', 'neon').catch((caught) => caught.message))
+  expect(error).toMatch(/must be one of/)
+})
+
+test('keeps Node out of the renderer', async () => {
+  expect(await session.window.evaluate(() => typeof require)).toBe('undefined')
+  expect(await session.window.evaluate(() => typeof process)).toBe('undefined')
+})
+
+test('navigates between views and keeps the route across reloads', async () => {
+  await session.window.click('[data-route="settings"]')
+  await expect(session.window.locator('form.settings')).toBeVisible()
+  await session.window.reload()
+  await expect(session.window.locator('form.settings')).toBeVisible()
+})
+
+test('runs jobs in a utility process and reports progress', async () => {
+  const updates = await session.window.evaluate(() => new Promise((resolve) => {
+    const seen = []
+    window.jobs.onUpdate((job) => {
+      seen.push(job)
+      if (job.state === 'completed') resolve(seen)
+    })
+    window.jobs.start('countPrimes', { limit: 1000000 })
+  }))
+  expect(updates.at(-1).result).toEqual({ count: 78498 })
+  expect(updates.some(({ partial }) => partial)).toBe(true)
+})
@@ -0,0 +1,105 @@
+const childProcess = require('child_process')
+const { EventEmitter } = require('events')
+const fs = require('fs')
+const Module = require('module')
+const os = require('os')
+const path = require('path')
+
+const root = path.join(__dirname, '..', '..')
+const sources = path.join(root, 'src')
+
+let current = null
+
+const load = Module._load
+Module._load = function (request, ...rest) {
+  if (request === 'electron') {
+    if (!current) throw new Error('Call setup() before requiring app modules')
+    return current
+  }
+  return load.call(this, request, ...rest)
+}
+
+const createElectron = (userData) => {
+  const app = Object.assign(new EventEmitter(), {
+    isPackaged: false,
+    getPath: (name) => (name === 'userData' ? userData : path.join(userData, name)),
+    setPath: () => {},
+    getAppPath: () => root,
+    getName: () => 'Zaphnath',
+    getVersion: () => '1.0.0',
+    getLocale: () => 'en-US',
+    getAppMetrics: () => [],
+    getGPUFeatureStatus: () => ({}),
+    setBadgeCount: () => true,
+    whenReady: () => Promise.resolve(),
+    quit: () => {},
+    exit: () => {},
+    relaunch: () => {},
+  })
+
+  const handlers = new Map()
+  const ipcMain = Object.assign(new EventEmitter(), {
+    handle: (name, handler) => {
+      if (handlers.has(name)) throw new Error(`Attempted to register a second handler for '${name}'`)
+      handlers.set(name, handler)
+    },
+    // Test-only: calls a handler the way ipcRenderer.invoke would.
+    invoke: (name, event, ...args) => handlers.get(name)(event, ...args),
+  })
+
+  const display = { id: 1, bounds: { x: 0, y: 0, width: 1920, height: 1080 }, workArea: { x: 0, y: 0, width: 1920, height: 1040 } }
+
+  return {
+    app,
+    ipcMain,
+    BrowserWindow: { fromWebContents: () => null, getFocusedWindow: () => null, getAllWindows: () => [] },
+    screen: {
+      getAllDisplays: () => [display],
+      getPrimaryDisplay: () => display,
+      getDisplayMatching: () => display,
+    },
+    nativeTheme: Object.assign(new EventEmitter(), { shouldUseDarkColors: false, themeSource: 'system' }),
+    shell: { openExternal: async () => {}, openPath: async () => '' },
+    clipboard: { writeText: () => {} },
+    crashReporter: { start: () => {}, addExtraParameter: () => {} },
+    dialog: { showSaveDialog: async () => ({ canceled: true }), showOpenDialog: async () => ({ canceled: true, filePaths: [] }), showMessageBox: async () => ({ response: 0 }) },
+    session: { defaultSession: { webRequest: { onHeadersReceived: () => {} }, setPermissionRequestHandler: () => {}, setPermissionCheckHandler: () => {} } },
+    protocol: { registerSchemesAsPrivileged: () => {}, registerBufferProtocol: () => {} },
+    utilityProcess: { fork: forkUtility },
+  }
+}
+
+// utilityProcess.fork backed by a plain Node child process.
+const forkUtility = (modulePath, args = []) => {
+  const child = childProcess.fork(modulePath, args, { execArgv: ['--require', path.join(__dirname, 'parent-port.js')] })
+  const utility = Object.assign(new EventEmitter(), {
+    get pid() {
+      return child.pid
+    },
+    postMessage: (message) => child.send(message),
+    kill: () => child.kill(),
+  })
+  child.on('spawn', () => utility.emit('spawn'))
+  child.on('message', (message) => utility.emit('message', message))
+  child.on('exit', (code) => utility.emit('exit', code === null ? -1 : code))
+  return utility
+}
+
+// Gives each test a fresh electron stub, a temporary userData directory and
+// fresh copies of the app modules, so module-level state never leaks
+// between tests.
+const setup = (overrides = {}) => {
+  const userData = fs.mkdtempSync(path.join(os.tmpdir(), 'zaphnath-test-'))
+  current = { ...createElectron(userData), ...overrides }
+  for (const key of Object.keys(require.cache)) {
+    if (key.startsWith(sources)) delete require.cache[key]
+  }
+  return {
+    electron: current,
+    userData,
+    require: (name) => require(path.join(sources, 'main', name)),
+    cleanup: () => fs.rmSync(userData, { recursive: true, force: true }),
+  }
+}
+
+module.exports = { setup }
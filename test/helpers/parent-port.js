@@ -0,0 +1,8 @@
+// Preloaded into workers the utilityProcess stub forks, giving them the
+// process.parentPort API a real utility process has.
+const { EventEmitter } = require('events')
+
+const parentPort = new EventEmitter()
+parentPort.postMessage = (message) => process.send(message)
+process.on('message', (data) => parentPort.emit('message', { data }))
+process.parentPort = parentPort
@@ -0,0 +1,41 @@
+const assert = require('assert/strict')
+const { afterEach, beforeEach, test } = require('node:test')
+const { setup } = require('../helpers/electron')
+
+let context
+
+beforeEach(() => {
+  context = setup()
+})
+
+afterEach(() => context.cleanup())
+
+test('rebinds, unbinds and resets shortcuts', () => {
+  const commands = context.require('commands')
+  const changes = []
+  commands.on('change', () => changes.push(commands.accelerator('window.new')))
+
+  assert.equal(commands.setAccelerator('window.new', 'CmdOrCtrl+Shift+N'), 'CmdOrCtrl+Shift+N')
+  assert.equal(commands.setAccelerator('window.new', ''), null)
+  assert.equal(commands.setAccelerator('window.new', null), 'CmdOrCtrl+N')
+  assert.deepEqual(changes, ['CmdOrCtrl+Shift+N', null, 'CmdOrCtrl+N'])
+  assert.deepEqual(context.require('settings').get('keybindings'), {})
+})
+
+test('rejects unknown commands and malformed shortcuts', () => {
+  const commands = context.require('commands')
+  assert.throws(() => commands.setAccelerator('nope', 'F5'), /Unknown command/)
+  for (const value of ['Ctrl+', 'Hyper+K', 'Shift+Shift+A', 'Ctrl+AB']) {
+    assert.throws(() => commands.setAccelerator('window.new', value), /not a valid shortcut/, value)
+  }
+})
+
+test('falls back to the default for hand-edited bindings that are invalid', () => {
+  context.require('settings').set('keybindings', { 'window.new': 'Ctrl+AB', 'gone.command': 'F5', 'app.quit': 'F10' })
+  const commands = context.require('commands')
+  assert.equal(commands.accelerator('window.new'), 'CmdOrCtrl+N')
+  assert.equal(commands.accelerator('app.quit'), 'F10')
+
+  commands.setAccelerator('view.reload', 'F5')
+  assert.deepEqual(Object.keys(context.require('settings').get('keybindings')).sort(), ['app.quit', 'view.reload', 'window.new'])
+})
@@ -0,0 +1,120 @@
+const assert = require('assert/strict')
+const fs = require('fs')
+const http = require('http')
+const path = require('path')
+const { afterEach, beforeEach, test } = require('node:test')
+const { setup } = require('../helpers/electron')
+
+let context
+let server
+let uploads
+
+beforeEach(async () => {
+  context = setup()
+  uploads = []
+  server = http.createServer(async (request, response) => {
+    const chunks = []
+    for await (const chunk of request) chunks.push(chunk)
+    uploads.push({ url: request.url, type: request.headers['content-type'], body: Buffer.concat(chunks).toString('latin1') })
+    response.end(`remote-${uploads.length}`)
+  })
+  await new Promise((resolve) => server.listen(0, '127.0.0.1', resolve))
+})
+
+afterEach(async () => {
+  await new Promise((resolve) => server.close(resolve))
+  context.cleanup()
+})
+
+const writeDump = (name, contents = 'MDMP', mtime = new Date()) => {
+  const file = path.join(context.userData, 'crashes', 'dumps', 'completed', `${name}.dmp`)
+  fs.mkdirSync(path.dirname(file), { recursive: true })
+  fs.writeFileSync(file, contents)
+  fs.utimesSync(file, mtime, mtime)
+  return file
+}
+
+const session = { appVersion: '0.9.0', activeWindow: 'main#1', lastIpc: ['ping', 'settings:get'] }
+
+test('records metadata for new minidumps once', async () => {
+  const file = writeDump('a1b2')
+  const crashes = context.require('crashes')
+
+  const [report] = await crashes.collect({ context: session, processType: 'renderer' })
+  assert.deepEqual(report, {
+    id: 'a1b2',
+    file,
+    createdAt: report.createdAt,
+    process: 'renderer',
+    appVersion: '0.9.0',
+    activeWindow: 'main#1',
+    lastIpc: ['ping', 'settings:get'],
+    uploadedAt: null,
+    remoteId: null,
+  })
+  assert.deepEqual(await crashes.collect(), [])
+  assert.deepEqual(crashes.list().map(({ id }) => id), ['a1b2'])
+})
+
+test('keeps only the most recent reports', async () => {
+  for (let i = 0; i < 25; i++) writeDump(`dump-${i}`, 'MDMP', new Date(2024, 0, i + 1))
+  const crashes = context.require('crashes')
+  await crashes.collect({ context: session })
+
+  const ids = crashes.list().map(({ id }) => id)
+  assert.equal(ids.length, 20)
+  assert.equal(ids[0], 'dump-24')
+  assert.equal(fs.existsSync(path.join(context.userData, 'crashes', 'dumps', 'completed', 'dump-0.dmp')), false)
+})
+
+test('uploads a report to the configured endpoint', async () => {
+  writeDump('c3d4', 'MDMP-contents')
+  context.require('settings').set('crashReports.endpoint', `http://127.0.0.1:${server.address().port}/submit`)
+  const crashes = context.require('crashes')
+  await crashes.collect({ context: session })
+
+  const report = await crashes.upload('c3d4')
+  assert.equal(report.remoteId, 'remote-1')
+  assert.ok(report.uploadedAt)
+  assert.equal(crashes.list()[0].remoteId, 'remote-1')
+
+  const [{ url, type, body }] = uploads
+  assert.equal(url, '/submit')
+  assert.match(type, /^multipart\/form-data; boundary=/)
+  assert.match(body, /name="version"\r\n\r\n0\.9\.0\r\n/)
+  assert.match(body, /name="upload_file_minidump"; filename="c3d4\.dmp"[^]*MDMP-contents/)
+})
+
+test('uploads new reports automatically when enabled', async () => {
+  const settings = context.require('settings')
+  settings.set('crashReports.endpoint', `http://127.0.0.1:${server.address().port}/submit`)
+  settings.set('crashReports.upload', true)
+  writeDump('e5f6')
+  const crashes = context.require('crashes')
+
+  await crashes.collect({ context: session })
+  assert.equal(uploads.length, 1)
+  assert.equal(crashes.list()[0].remoteId, 'remote-1')
+})
+
+test('refuses to upload without an endpoint', async () => {
+  writeDump('a1b2')
+  const crashes = context.require('crashes')
+  await crashes.collect({ context: session })
+  await assert.rejects(crashes.upload('a1b2'), /No crash report endpoint/)
+  await assert.rejects(crashes.upload('missing'), /Unknown crash report/)
+})
+
+test('annotates reports with the renderer calls made since startup', async () => {
+  const extras = {}
+  context.electron.crashReporter.addExtraParameter = (key, value) => {
+    extras[key] = value
+  }
+  const crashes = context.require('crashes')
+  const ipc = context.require('ipc')
+  crashes.start()
+  ipc.handle('ping', () => 'pong')
+
+  await context.electron.ipcMain.invoke('ping', { sender: { id: 1 } })
+  assert.equal(extras.lastIpc, 'ping')
+})
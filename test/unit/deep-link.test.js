@@ -0,0 +1,49 @@
+const assert = require('assert/strict')
+const { EventEmitter } = require('events')
+const { afterEach, beforeEach, test } = require('node:test')
+const { setup } = require('../helpers/electron')
+
+let context
+
+beforeEach(() => {
+  context = setup()
+})
+
+afterEach(() => context.cleanup())
+
+test('parses known actions with valid parameters', () => {
+  const { parse } = context.require('deep-link')
+  assert.deepEqual(parse('zaphnath://open?view=settings'), { action: 'open', params: { view: 'settings' } })
+})
+
+test('rejects anything outside the route table', () => {
+  const { parse } = context.require('deep-link')
+  const rejected = {
+    'zaphnath://open?view=evil': /must be one of/,
+    'zaphnath://open?view=home&view=about': /Duplicate parameter/,
+    'zaphnath://open?view=home&channel=ping': /not allowed/,
+    'zaphnath://constructor': /Unknown action/,
+    'zaphnath://open/extra?view=home': /Unexpected path/,
+    'https://open?view=home': /Not a zaphnath/,
+    [`zaphnath://open?view=${'a'.repeat(3000)}`]: /too long/,
+  }
+  for (const [url, error] of Object.entries(rejected)) assert.throws(() => parse(url), error, url)
+})
+
+test('recognizes deep links case-insensitively', () => {
+  const { isDeepLink } = context.require('deep-link')
+  assert.equal(isDeepLink('ZAPHNATH://open?view=home'), true)
+  assert.equal(isDeepLink('https://example.com'), false)
+})
+
+test('waits for a loading main window before navigating it', () => {
+  const sent = []
+  const webContents = Object.assign(new EventEmitter(), { isLoading: () => true, send: (...args) => sent.push(args) })
+  const win = { webContents, isMinimized: () => false, isVisible: () => false, show: () => {}, focus: () => {} }
+  context.require('windows').find = () => ({ win })
+  context.require('deep-link').handle('zaphnath://open?view=settings')
+
+  assert.deepEqual(sent, [])
+  webContents.emit('did-finish-load')
+  assert.deepEqual(sent, [['app:navigate', { view: 'settings' }]])
+})
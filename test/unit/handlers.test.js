@@ -0,0 +1,41 @@
+const assert = require('assert/strict')
+const fs = require('fs')
+const path = require('path')
+const { afterEach, beforeEach, test } = require('node:test')
+const { setup } = require('../helpers/electron')
+
+let context
+
+beforeEach(() => {
+  context = setup()
+  context.electron.app.isPackaged = true
+  context.require('handlers').registerHandlers()
+})
+
+afterEach(() => context.cleanup())
+
+const entries = () => fs.readFileSync(path.join(context.userData, 'logs', 'main.log'), 'utf8').trim().split('\n').map((line) => JSON.parse(line))
+
+test('logs renderer messages from untracked webContents', () => {
+  context.electron.ipcMain.emit('log:write', { sender: { id: 9 } }, 'info', 'hello', { process: 'main' })
+  const [entry] = entries()
+  assert.deepEqual([entry.scope, entry.process, entry.message], ['webContents#9', 'renderer', 'hello'])
+})
+
+test('truncates oversized renderer log fields', () => {
+  context.electron.ipcMain.emit('log:write', { sender: { id: 9 } }, 'info', 'big', { blob: 'x'.repeat(100 * 1024) })
+  context.electron.ipcMain.emit('log:write', { sender: { id: 9 } }, 'info', 'small', { user: 'ada' })
+  const [big, small] = entries()
+  assert.equal(big.truncated, true)
+  assert.equal(big.fields.length, 8 * 1024)
+  assert.equal(big.blob, undefined)
+  assert.equal(small.user, 'ada')
+})
+
+test('checks job params against the schema for their task', async () => {
+  const start = (...args) => context.electron.ipcMain.invoke('jobs:start', { sender: { id: 1 } }, ...args)
+  await assert.rejects(start('countPrimes', {}), /does not match any allowed shape/)
+  await assert.rejects(start('countPrimes', { limit: 10, ms: 5 }), /does not match any allowed shape/)
+  await assert.rejects(start('wait', { steps: 2 }), /does not match any allowed shape/)
+  await assert.rejects(start('countPrimes', { ms: 5 }), /limit is required/)
+})
@@ -0,0 +1,58 @@
+const assert = require('assert/strict')
+const fs = require('fs')
+const path = require('path')
+const { afterEach, beforeEach, test } = require('node:test')
+const { setup } = require('../helpers/electron')
+
+const locales = path.join(__dirname, '..', '..', 'locales')
+
+let context
+
+beforeEach(() => {
+  context = setup()
+})
+
+afterEach(() => context.cleanup())
+
+test('detects the system locale and falls back by language', () => {
+  context.electron.app.getLocale = () => 'am-ET'
+  const i18n = context.require('i18n')
+  i18n.start()
+  assert.equal(i18n.locale(), 'am')
+  assert.equal(i18n.t('command.app.quit'), 'ውጣ')
+})
+
+test('falls back to English for unknown locales and missing keys', () => {
+  context.electron.app.getLocale = () => 'xx-YY'
+  const i18n = context.require('i18n')
+  i18n.start()
+  assert.equal(i18n.locale(), 'en')
+  assert.equal(i18n.t('no.such.key'), 'no.such.key')
+})
+
+test('interpolates and pluralizes', () => {
+  const i18n = context.require('i18n')
+  i18n.start()
+  assert.equal(i18n.t('home.opened', { file: 'a.txt' }), 'Opened a.txt')
+  assert.equal(i18n.t('diagnostics.processes', { count: 1 }), '1 process')
+  assert.equal(i18n.t('diagnostics.processes', { count: 3 }), '3 processes')
+  assert.equal(i18n.format('ar', { zero: 'z', two: 'd', other: 'o' }, { count: 2 }), 'd')
+})
+
+test('switches locale at runtime from settings', () => {
+  const i18n = context.require('i18n')
+  i18n.start()
+  const changes = []
+  i18n.on('change', (locale) => changes.push(locale))
+  context.require('settings').set('general.locale', 'ar')
+  assert.deepEqual(changes, ['ar'])
+  assert.equal(i18n.catalog().dir, 'rtl')
+})
+
+test('every catalog defines the same keys as English', () => {
+  const read = (name) => JSON.parse(fs.readFileSync(path.join(locales, name), 'utf8'))
+  const english = Object.keys(read('en.json')).sort()
+  for (const name of fs.readdirSync(locales)) {
+    assert.deepEqual(Object.keys(read(name)).sort(), english, name)
+  }
+})
@@ -0,0 +1,79 @@
+const assert = require('assert/strict')
+const { EventEmitter } = require('events')
+const { afterEach, beforeEach, test } = require('node:test')
+const { setup } = require('../helpers/electron')
+
+let context
+
+beforeEach(() => {
+  context = setup()
+})
+
+afterEach(() => context.cleanup())
+
+const sender = (id = 1) => ({ id })
+
+test('dispatches valid calls to the handler with window context', async () => {
+  const ipc = context.require('ipc')
+  ipc.handle('ping', ({ sender: from }) => `pong from ${from.id}`)
+  assert.equal(await context.electron.ipcMain.invoke('ping', { sender: sender(7) }), 'pong from 7')
+})
+
+test('rejects invalid arguments before the handler runs', async () => {
+  const ipc = context.require('ipc')
+  let called = false
+  ipc.handle('windows:open', () => {
+    called = true
+    return 1
+  })
+  await assert.rejects(context.electron.ipcMain.invoke('windows:open', { sender: sender() }, 'nope'), /must be one of/)
+  assert.equal(called, false)
+})
+
+test('rejects handler results that do not match the declared return schema', async () => {
+  const ipc = context.require('ipc')
+  ipc.handle('ping', () => 42)
+  await assert.rejects(context.electron.ipcMain.invoke('ping', { sender: sender() }), /ping result must be a string/)
+})
+
+test('refuses unknown channels and duplicate handlers', () => {
+  const ipc = context.require('ipc')
+  assert.throws(() => ipc.handle('nope', () => {}), /Unknown IPC channel/)
+  ipc.handle('ping', () => 'pong')
+  assert.throws(() => ipc.handle('ping', () => 'pong'), /already registered/)
+})
+
+test('prefers window-scoped handlers for their own window', async () => {
+  const ipc = context.require('ipc')
+  const contents = Object.assign(new EventEmitter(), { id: 2 })
+  ipc.handle('ping', () => 'global')
+  ipc.handleForWindow({ id: 9, webContents: contents }, 'ping', () => 'scoped')
+  assert.equal(await context.electron.ipcMain.invoke('ping', { sender: sender(2) }), 'scoped')
+  assert.equal(await context.electron.ipcMain.invoke('ping', { sender: sender(3) }), 'global')
+  contents.emit('destroyed')
+  assert.equal(await context.electron.ipcMain.invoke('ping', { sender: sender(2) }), 'global')
+})
+
+test('rejects calls from windows without a scoped handler when there is no global one', async () => {
+  const ipc = context.require('ipc')
+  const contents = Object.assign(new EventEmitter(), { id: 2 })
+  ipc.handleForWindow({ id: 9, webContents: contents }, 'ping', () => 'scoped')
+  await assert.rejects(context.electron.ipcMain.invoke('ping', { sender: sender(3) }), /No handler/)
+})
+
+test('drops invalid fire-and-forget messages', () => {
+  const ipc = context.require('ipc')
+  const received = []
+  ipc.on('log:write', (ctx, level, message) => received.push([level, message]))
+  context.electron.ipcMain.emit('log:write', { sender: sender() }, 'info', 'hello')
+  context.electron.ipcMain.emit('log:write', { sender: sender() }, 'loud', 'hello')
+  assert.deepEqual(received, [['info', 'hello']])
+})
+
+test('serves the bridge manifest synchronously', () => {
+  context.require('ipc')
+  const event = {}
+  context.electron.ipcMain.emit('ipc:manifest', event)
+  assert.deepEqual(event.returnValue.find(({ name }) => name === 'ping'), { name: 'ping', kind: 'invoke', bridge: ['versions', 'ping'] })
+  assert.ok(event.returnValue.every(({ bridge }) => Array.isArray(bridge)))
+})
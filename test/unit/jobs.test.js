@@ -0,0 +1,93 @@
+const assert = require('assert/strict')
+const { afterEach, beforeEach, test } = require('node:test')
+const { setup } = require('../helpers/electron')
+
+let context
+let jobs
+
+beforeEach(() => {
+  context = setup()
+  jobs = context.require('jobs')
+})
+
+afterEach(() => {
+  jobs.stop()
+  context.cleanup()
+})
+
+const until = (predicate) => new Promise((resolve) => {
+  const listener = (job) => {
+    if (!predicate(job)) return
+    jobs.removeListener('update', listener)
+    resolve(job)
+  }
+  jobs.on('update', listener)
+})
+
+const finished = (id) => until((job) => job.id === id && ['completed', 'failed', 'cancelled'].includes(job.state))
+
+test('runs a job in a worker and reports progress', async () => {
+  const updates = []
+  jobs.on('update', (job) => updates.push(job))
+  const { id } = jobs.start('countPrimes', { limit: 1000000 })
+
+  const job = await finished(id)
+  assert.equal(job.state, 'completed')
+  assert.deepEqual(job.result, { count: 78498 })
+  assert.deepEqual(updates.map(({ state }) => state).filter((state, index, all) => state !== all[index - 1]), ['queued', 'running', 'completed'])
+  const partials = updates.filter(({ partial }) => partial).map(({ partial }) => partial.count)
+  assert.ok(partials.length > 0)
+  assert.deepEqual(partials, [...partials].sort((a, b) => a - b))
+})
+
+test('queues jobs beyond the concurrency limit', async () => {
+  context.require('settings').set('jobs.concurrency', 1)
+  const first = jobs.start('wait', { ms: 200, steps: 2 })
+  const second = jobs.start('wait', { ms: 10, steps: 1 })
+  assert.equal(jobs.get(first.id).state, 'running')
+  assert.equal(jobs.get(second.id).state, 'queued')
+
+  await finished(second.id)
+  assert.ok(jobs.get(second.id).startedAt >= jobs.get(first.id).finishedAt)
+})
+
+test('cancels queued and running jobs', async () => {
+  context.require('settings').set('jobs.concurrency', 1)
+  const running = jobs.start('wait', { ms: 60000, steps: 600 })
+  const queued = jobs.start('wait', { ms: 10 })
+
+  assert.equal(jobs.cancel(queued.id), true)
+  assert.equal(jobs.get(queued.id).state, 'cancelled')
+  const done = finished(running.id)
+  assert.equal(jobs.cancel(running.id), true)
+  assert.equal((await done).state, 'cancelled')
+  assert.equal(jobs.cancel(running.id), false)
+})
+
+test('fails the job when its worker crashes and keeps going', async () => {
+  const workers = []
+  const { fork } = context.electron.utilityProcess
+  context.electron.utilityProcess.fork = (...args) => {
+    const worker = fork(...args)
+    workers.push(worker)
+    return worker
+  }
+  const started = until((job) => job.state === 'running')
+  const { id } = jobs.start('wait', { ms: 60000, steps: 600 })
+  await started
+  process.kill(workers[0].pid, 'SIGKILL')
+
+  const job = await finished(id)
+  assert.equal(job.state, 'failed')
+  assert.match(job.error, /Worker exited unexpectedly/)
+
+  const next = jobs.start('countPrimes', { limit: 100 })
+  assert.deepEqual((await finished(next.id)).result, { count: 25 })
+})
+
+test('fails jobs whose task throws', async () => {
+  const { id } = jobs.start('nope', {})
+  const job = await finished(id)
+  assert.equal(job.state, 'failed')
+  assert.equal(job.error, "Unknown task 'nope'")
+})
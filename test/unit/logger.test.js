@@ -0,0 +1,51 @@
+const assert = require('assert/strict')
+const fs = require('fs')
+const path = require('path')
+const { afterEach, beforeEach, test } = require('node:test')
+const { setup } = require('../helpers/electron')
+
+let context
+
+beforeEach(() => {
+  context = setup()
+  context.electron.app.isPackaged = true
+})
+
+afterEach(() => context.cleanup())
+
+const read = (name) => fs.readFileSync(path.join(context.userData, 'logs', name), 'utf8').trim().split('\n').map((line) => JSON.parse(line))
+
+test('writes scoped JSON lines with serialized errors', () => {
+  const logger = context.require('logger')
+  logger.createLogger('test').child('unit').error('Failed', { error: new Error('boom') })
+  const [entry] = read('main.log')
+  assert.equal(entry.level, 'error')
+  assert.equal(entry.scope, 'test:unit')
+  assert.equal(entry.process, 'main')
+  assert.equal(entry.error.message, 'boom')
+  assert.match(entry.error.stack, /boom/)
+})
+
+test('drops entries below the configured level', () => {
+  const logger = context.require('logger')
+  logger.createLogger('test').debug('hidden')
+  logger.createLogger('test').info('shown')
+  assert.deepEqual(read('main.log').map(({ message }) => message), ['shown'])
+})
+
+test('rotates files once they exceed the size limit', () => {
+  const logger = context.require('logger')
+  const log = logger.createLogger('test')
+  const padding = 'x'.repeat(64 * 1024)
+  for (let index = 0; index < 480; index += 1) log.info('entry', { index, padding })
+  const names = fs.readdirSync(path.join(context.userData, 'logs')).sort()
+  assert.deepEqual(names, ['main.1.log', 'main.2.log', 'main.3.log', 'main.4.log', 'main.log'])
+  assert.equal(read('main.log').at(-1).index, 479)
+})
+
+test('keeps caller fields from overwriting the entry envelope', () => {
+  const logger = context.require('logger')
+  logger.log('info', 'renderer#3', 'x', { process: 'main', scope: 'updater', level: 'error', detail: 1 }, 'renderer')
+  const [entry] = read('main.log')
+  assert.deepEqual([entry.level, entry.scope, entry.process, entry.detail], ['info', 'renderer#3', 'renderer', 1])
+})
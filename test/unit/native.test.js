@@ -0,0 +1,93 @@
+const assert = require('assert/strict')
+const fs = require('fs')
+const path = require('path')
+const { afterEach, beforeEach, test } = require('node:test')
+const { setup } = require('../helpers/electron')
+
+const addonBuilt = fs.existsSync(path.join(__dirname, '..', '..', 'native', 'zaphnath.node'))
+
+let context
+
+beforeEach(() => {
+  context = setup()
+})
+
+afterEach(() => {
+  delete process.env.ZAPHNATH_NATIVE
+  context.cleanup()
+})
+
+const writeFile = (name, contents) => {
+  const file = path.join(context.userData, name)
+  fs.writeFileSync(file, contents)
+  return file
+}
+
+test('falls back to JavaScript when asked to', async () => {
+  process.env.ZAPHNATH_NATIVE = '0'
+  const native = context.require('native')
+  assert.equal(native.backend(), 'javascript')
+  assert.equal(await native.sha256(Buffer.from('abc')), 'ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad')
+  assert.equal((await native.gunzip(await native.gzip(Buffer.from('zaphnath'), 9))).toString(), 'zaphnath')
+})
+
+test('inspects files across chunk boundaries', async () => {
+  const { fallback } = context.require('native')
+  const long = 'x'.repeat(64 * 1024 + 10)
+  assert.deepEqual(await fallback.inspectFile(writeFile('chunks.txt', `${long}\ntail`)), {
+    bytes: long.length + 5,
+    lines: 2,
+    longestLine: long.length,
+    sha256: await fallback.sha256(Buffer.from(`${long}\ntail`)),
+  })
+  assert.equal((await fallback.inspectFile(writeFile('trailing.txt', 'one\nthree\n'))).lines, 2)
+  assert.equal((await fallback.inspectFile(writeFile('empty.txt', ''))).lines, 0)
+  await assert.rejects(fallback.inspectFile(path.join(context.userData, 'missing.txt')))
+})
+
+test('addon matches the JavaScript fallback', { skip: !addonBuilt && 'run npm run build:native first' }, async () => {
+  const native = context.require('native')
+  assert.equal(native.backend(), 'native')
+  const data = Buffer.from('line one\nline two\r\n'.repeat(5000))
+  const file = writeFile('data.txt', data)
+
+  assert.equal(await native.sha256(data), await native.fallback.sha256(data))
+  assert.deepEqual(await native.gunzip(await native.gzip(data)), data)
+  assert.deepEqual(await native.fallback.gunzip(await native.gzip(data, 1)), data)
+  const members = Buffer.concat([await native.gzip(data.subarray(0, 10)), await native.fallback.gzip(data.subarray(10))])
+  assert.deepEqual(await native.gunzip(members), await native.fallback.gunzip(members))
+  const bomb = await native.fallback.gzip(Buffer.alloc(64 * 1024 * 1024 + 1), 9)
+  await assert.rejects(native.gunzip(bomb), /exceeds/)
+  assert.deepEqual({ ...await native.inspectFile(file) }, await native.fallback.inspectFile(file))
+})
+
+test('accepts plain byte arrays as sent over IPC', async () => {
+  const native = context.require('native')
+  const bytes = new Uint8Array([0, 97, 98, 99, 0]).subarray(1, 4)
+  assert.equal(await native.sha256(bytes), await native.fallback.sha256(Buffer.from('abc')))
+  assert.equal((await native.gunzip(await native.gzip(bytes))).toString(), 'abc')
+})
+
+test('caps the size of decompressed output', async () => {
+  process.env.ZAPHNATH_NATIVE = '0'
+  const native = context.require('native')
+  const bomb = await native.gzip(Buffer.alloc(64 * 1024 * 1024 + 1), 9)
+  await assert.rejects(native.gunzip(bomb), /larger than/)
+  const members = Buffer.concat([await native.gzip(Buffer.from('first ')), await native.gzip(Buffer.from('second'))])
+  assert.equal((await native.gunzip(members)).toString(), 'first second')
+})
+
+test('inspects only files the user picked', async () => {
+  const file = writeFile('picked.txt', 'a\nbb\n')
+  const native = context.require('native')
+  assert.equal(await native.chooseAndInspect(null), null)
+
+  context.electron.dialog.showOpenDialog = async () => ({ canceled: false, filePaths: [file] })
+  assert.deepEqual({ ...await native.chooseAndInspect(null) }, {
+    name: 'picked.txt',
+    bytes: 5,
+    lines: 2,
+    longestLine: 2,
+    sha256: await native.fallback.sha256(Buffer.from('a\nbb\n')),
+  })
+})
@@ -0,0 +1,27 @@
+const assert = require('assert/strict')
+const path = require('path')
+const { afterEach, beforeEach, test } = require('node:test')
+const { setup } = require('../helpers/electron')
+
+let context
+
+beforeEach(() => {
+  context = setup()
+})
+
+afterEach(() => {
+  delete process.defaultApp
+  context.cleanup()
+})
+
+test('skips the executable, app path and switches', () => {
+  process.defaultApp = true
+  const { targets } = context.require('open')
+  const argv = ['/usr/bin/electron', '.', '--allow-file-access-from-files', 'notes.txt', 'zaphnath://open?view=about']
+  assert.deepEqual(targets(argv, '/home/user'), [path.resolve('/home/user', 'notes.txt'), 'zaphnath://open?view=about'])
+})
+
+test('keeps every argument after the executable in packaged builds', () => {
+  const { targets } = context.require('open')
+  assert.deepEqual(targets(['/opt/zaphnath/zaphnath', '/tmp/a.txt'], '/'), ['/tmp/a.txt'])
+})
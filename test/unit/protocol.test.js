@@ -0,0 +1,44 @@
+const assert = require('assert/strict')
+const path = require('path')
+const { afterEach, beforeEach, test } = require('node:test')
+const { setup } = require('../helpers/electron')
+
+const root = path.join(__dirname, '..', '..')
+
+let context
+
+beforeEach(() => {
+  context = setup()
+})
+
+afterEach(() => context.cleanup())
+
+test('maps app URLs to public files', () => {
+  const { resolve } = context.require('protocol')
+  assert.equal(resolve('app://zaphnath/'), path.join(root, 'index.html'))
+  assert.equal(resolve('app://zaphnath/index.html?window=main#/home'), path.join(root, 'index.html'))
+  assert.equal(resolve('app://zaphnath/src/renderer/index.js'), path.join(root, 'src', 'renderer', 'index.js'))
+})
+
+test('refuses paths outside the public set', () => {
+  const { resolve } = context.require('protocol')
+  for (const url of [
+    'app://zaphnath/main.js',
+    'app://zaphnath/src/main/ipc.js',
+    'app://zaphnath/%2e%2e/%2e%2e/etc/passwd',
+    'app://zaphnath/src/renderer/..%2f..%2fmain%2fipc.js',
+    'app://zaphnath/index.html%00.js',
+    'app://elsewhere/index.html',
+    'file:///etc/passwd',
+  ]) {
+    assert.equal(resolve(url), null, url)
+  }
+})
+
+test('builds app URLs with query parameters', () => {
+  const { appUrl, isAppUrl } = context.require('protocol')
+  const url = appUrl('index.html', { window: 'document', file: '/tmp/a b.txt' })
+  assert.equal(url, 'app://zaphnath/index.html?window=document&file=%2Ftmp%2Fa+b.txt')
+  assert.equal(isAppUrl(url), true)
+  assert.equal(isAppUrl('https://zaphnath/index.html'), false)
+})
@@ -0,0 +1,136 @@
+const assert = require('assert/strict')
+const { EventEmitter } = require('events')
+const fs = require('fs')
+const path = require('path')
+const { afterEach, beforeEach, test } = require('node:test')
+const { setup } = require('../helpers/electron')
+
+let context
+let prompts
+let answer
+let listeners
+
+const createWindow = (id) => {
+  const webContents = {
+    id,
+    reloads: 0,
+    crashed: false,
+    isDestroyed: () => false,
+    reload: () => { webContents.reloads += 1 },
+    forcefullyCrashRenderer: () => { webContents.crashed = true },
+  }
+  return Object.assign(new EventEmitter(), {
+    id,
+    webContents,
+    closed: false,
+    getTitle: () => 'Zaphnath',
+    isDestroyed: () => false,
+    close() { this.closed = true },
+  })
+}
+
+beforeEach(() => {
+  prompts = []
+  answer = async () => ({ response: 0 })
+  context = setup({
+    dialog: {
+      showMessageBox: (win, options) => {
+        prompts.push(options)
+        return answer(options)
+      },
+      showErrorBox: () => {},
+    },
+  })
+  listeners = {
+    uncaughtException: process.listeners('uncaughtException'),
+    unhandledRejection: process.listeners('unhandledRejection'),
+  }
+})
+
+afterEach(() => {
+  for (const [name, before] of Object.entries(listeners)) {
+    for (const listener of process.listeners(name)) {
+      if (!before.includes(listener)) process.removeListener(name, listener)
+    }
+  }
+  context.cleanup()
+})
+
+const start = () => {
+  const recovery = context.require('recovery')
+  recovery.start()
+  return recovery
+}
+
+const gone = (win, reason = 'crashed') =>
+  context.electron.app.listeners('render-process-gone')[0]({}, win.webContents, { reason, exitCode: 1 })
+
+test('reloads a crashed renderer', async () => {
+  start()
+  const win = createWindow(1)
+  await gone(win)
+  assert.equal(win.webContents.reloads, 1)
+  assert.equal(prompts.length, 0)
+})
+
+test('ignores renderers that exit cleanly', async () => {
+  start()
+  const win = createWindow(1)
+  await gone(win, 'clean-exit')
+  assert.equal(win.webContents.reloads, 0)
+})
+
+test('asks before reloading a renderer stuck in a crash loop', async () => {
+  const { MAX_CRASHES } = start()
+  const win = createWindow(1)
+  context.electron.BrowserWindow.fromWebContents = () => win
+  answer = async () => ({ response: 1 })
+
+  for (let i = 0; i < MAX_CRASHES; i++) await gone(win)
+  assert.equal(prompts.length, 0)
+  await gone(win)
+  assert.equal(prompts.length, 1)
+  assert.equal(win.webContents.reloads, MAX_CRASHES)
+  assert.equal(win.closed, true)
+})
+
+test('restarts a hung renderer when the user chooses reload', async () => {
+  start()
+  const win = createWindow(2)
+  context.electron.app.emit('browser-window-created', {}, win)
+  answer = async () => ({ response: 1 })
+
+  win.emit('unresponsive')
+  await new Promise(setImmediate)
+  assert.equal(prompts.length, 1)
+  assert.equal(win.webContents.crashed, true)
+
+  await gone(win, 'killed')
+  assert.equal(win.webContents.reloads, 1)
+})
+
+test('closes the prompt when a hung window recovers', async () => {
+  start()
+  const win = createWindow(3)
+  context.electron.app.emit('browser-window-created', {}, win)
+  answer = ({ signal }) => new Promise((resolve) => signal.addEventListener('abort', () => resolve({ response: 0 })))
+
+  win.emit('unresponsive')
+  win.emit('unresponsive')
+  assert.equal(prompts.length, 1)
+  win.emit('responsive')
+  await new Promise(setImmediate)
+  assert.equal(prompts[0].signal.aborted, true)
+  assert.equal(win.webContents.crashed, false)
+})
+
+test('logs uncaught exceptions and exits', () => {
+  const codes = []
+  context.electron.app.exit = (code) => codes.push(code)
+  context.electron.app.isReady = () => true
+  start()
+  const [listener] = process.listeners('uncaughtException').filter((candidate) => !listeners.uncaughtException.includes(candidate))
+  listener(new Error('boom'), 'uncaughtException')
+  assert.deepEqual(codes, [1])
+  assert.match(fs.readFileSync(path.join(context.userData, 'logs', 'main.log'), 'utf8'), /Uncaught exception in main process/)
+})
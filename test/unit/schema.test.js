@@ -0,0 +1,60 @@
+const assert = require('assert/strict')
+const { test } = require('node:test')
+const { check, validate } = require('../../src/main/schema')
+
+test('accepts values matching the schema', () => {
+  const schema = {
+    type: 'object',
+    properties: {
+      name: { type: 'string', maxLength: 8 },
+      count: { type: 'number', integer: true, minimum: 0 },
+      tags: { type: 'array', items: { enum: ['a', 'b'] }, optional: true },
+    },
+  }
+  assert.deepEqual(validate(schema, { name: 'zaph', count: 2 }), [])
+  assert.deepEqual(validate(schema, { name: 'zaph', count: 2, tags: ['a'] }), [])
+})
+
+test('reports every mismatch with its path', () => {
+  const schema = { type: 'object', properties: { name: { type: 'string' }, count: { type: 'number', integer: true } } }
+  assert.deepEqual(validate(schema, { name: 1, count: 1.5, extra: true }), [
+    'value.name must be a string, got number',
+    'value.count must be an integer',
+    'value.extra is not allowed',
+  ])
+})
+
+test('does not treat inherited property names as declared', () => {
+  const schema = { type: 'object', properties: { view: { type: 'string' } } }
+  assert.deepEqual(validate(schema, { view: 'home', constructor: 'x' }), ['value.constructor is not allowed'])
+})
+
+test('validates additional properties against their schema', () => {
+  const schema = { type: 'object', additionalProperties: { type: 'string' } }
+  assert.deepEqual(validate(schema, { a: 'x' }), [])
+  assert.deepEqual(validate(schema, { a: 1 }), ['value.a must be a string, got number'])
+})
+
+test('requires values unless optional', () => {
+  assert.deepEqual(validate({ type: 'string' }, undefined), ['value is required'])
+  assert.deepEqual(validate({ type: 'string', optional: true }, undefined), [])
+})
+
+test('oneOf accepts any matching alternative', () => {
+  const schema = { oneOf: [{ type: 'string' }, { type: 'null' }] }
+  assert.deepEqual(validate(schema, null), [])
+  assert.deepEqual(validate(schema, 'x'), [])
+  assert.equal(validate(schema, 1).length, 1)
+})
+
+test('check throws a TypeError describing the failure', () => {
+  assert.throws(() => check({ type: 'boolean' }, 'yes', 'flag'), { name: 'TypeError', message: 'flag must be boolean, got string' })
+  assert.equal(check({ type: 'boolean' }, true, 'flag'), true)
+})
+
+test('checks byte arrays separately from objects', () => {
+  assert.deepEqual(validate({ type: 'bytes', maxLength: 4 }, Buffer.from('abc')), [])
+  assert.deepEqual(validate({ type: 'bytes', maxLength: 2 }, new Uint8Array(3)), ['value must be at most 2 bytes'])
+  assert.deepEqual(validate({ type: 'bytes' }, 'abc'), ['value must be bytes, got string'])
+  assert.deepEqual(validate({ type: 'object' }, new Uint8Array(1)), ['value must be an object, got bytes'])
+})
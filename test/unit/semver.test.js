@@ -0,0 +1,23 @@
+const assert = require('assert/strict')
+const { test } = require('node:test')
+const semver = require('../../src/main/semver')
+
+test('parses and validates versions', () => {
+  assert.deepEqual(semver.parse('v1.2.3-beta.1+build.5'), { major: 1, minor: 2, patch: 3, prerelease: ['beta', '1'], build: ['build', '5'] })
+  assert.equal(semver.valid('1.2'), false)
+  assert.equal(semver.valid('01.2.3'), false)
+})
+
+test('orders versions per semver precedence', () => {
+  const ordered = ['1.0.0-alpha', '1.0.0-alpha.1', '1.0.0-alpha.beta', '1.0.0-beta', '1.0.0-beta.2', '1.0.0-beta.11', '1.0.0-rc.1', '1.0.0', '1.0.1', '1.10.0', '2.0.0']
+  for (let index = 1; index < ordered.length; index += 1) {
+    assert.equal(semver.compare(ordered[index - 1], ordered[index]), -1, `${ordered[index - 1]} < ${ordered[index]}`)
+    assert.equal(semver.compare(ordered[index], ordered[index - 1]), 1)
+  }
+  assert.equal(semver.compare('1.0.0+a', '1.0.0+b'), 0)
+})
+
+test('detects prereleases', () => {
+  assert.equal(semver.isPrerelease('2.0.0-beta.1'), true)
+  assert.equal(semver.isPrerelease('2.0.0'), false)
+})
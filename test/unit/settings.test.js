@@ -0,0 +1,62 @@
+const assert = require('assert/strict')
+const fs = require('fs')
+const path = require('path')
+const { afterEach, beforeEach, test } = require('node:test')
+const { setup } = require('../helpers/electron')
+
+let context
+
+beforeEach(() => {
+  context = setup()
+})
+
+afterEach(() => context.cleanup())
+
+const file = () => path.join(context.userData, 'settings.json')
+
+test('returns defaults when nothing is stored', () => {
+  const settings = context.require('settings')
+  assert.equal(settings.get('window.restoreState'), true)
+  assert.equal(settings.get('appearance.theme'), 'system')
+})
+
+test('persists changes atomically and emits change events', () => {
+  const settings = context.require('settings')
+  const changes = []
+  settings.on('change', (change) => changes.push(change))
+  settings.set('appearance.theme', 'dark')
+  settings.set('appearance.theme', 'dark')
+  assert.deepEqual(changes, [{ key: 'appearance.theme', value: 'dark', previous: 'system' }])
+  const stored = JSON.parse(fs.readFileSync(file(), 'utf8'))
+  assert.equal(stored.values['appearance.theme'], 'dark')
+  assert.deepEqual(fs.readdirSync(context.userData).filter((name) => name.endsWith('.tmp')), [])
+})
+
+test('rejects unknown keys and invalid values', () => {
+  const settings = context.require('settings')
+  assert.throws(() => settings.set('nope', 1), /Unknown setting/)
+  assert.throws(() => settings.set('appearance.theme', 'blue'), /must be one of/)
+})
+
+test('lets renderers change only the settings marked for them', () => {
+  const settings = context.require('settings')
+  assert.equal(settings.setFromRenderer('appearance.theme', 'dark'), 'dark')
+  for (const key of ['security.allowedPermissions', 'updates.feedUrl', 'crashReports.endpoint']) {
+    assert.throws(() => settings.setFromRenderer(key, settings.get(key)), /cannot be changed from a renderer/)
+  }
+  assert.throws(() => settings.setFromRenderer('nope', 1), /Unknown setting/)
+})
+
+test('ignores invalid stored values and unknown keys', () => {
+  fs.writeFileSync(file(), JSON.stringify({ version: 1, values: { 'appearance.theme': 'blue', 'tray.enabled': true, old: 1 } }))
+  const settings = context.require('settings')
+  assert.equal(settings.get('appearance.theme'), 'system')
+  assert.equal(settings.get('tray.enabled'), true)
+  assert.equal('old' in settings.get(), false)
+})
+
+test('reads files written before versioning', () => {
+  fs.writeFileSync(file(), JSON.stringify({ values: { 'tray.enabled': true } }))
+  const settings = context.require('settings')
+  assert.equal(settings.get('tray.enabled'), true)
+})
@@ -0,0 +1,108 @@
+const assert = require('assert/strict')
+const { EventEmitter } = require('events')
+const fs = require('fs')
+const path = require('path')
+const { afterEach, beforeEach, test } = require('node:test')
+const { setup } = require('../helpers/electron')
+
+const executable = process.platform === 'win32' ? 'zaphnath-sidecar.exe' : 'zaphnath-sidecar'
+const built = ['release', 'debug'].some((profile) => fs.existsSync(path.join(__dirname, '..', '..', 'target', profile, executable)))
+const skip = !built && 'run cargo build -p zaphnath-sidecar first'
+
+let context
+let sidecar
+
+beforeEach(() => {
+  context = setup()
+  sidecar = context.require('sidecar')
+})
+
+afterEach(async () => {
+  delete process.env.ZAPHNATH_SIDECAR
+  await sidecar.stop()
+  context.cleanup()
+})
+
+const waitFor = (state) => new Promise((resolve) => {
+  if (sidecar.status().state === state) return resolve(sidecar.status())
+  const listener = (status) => {
+    if (status.state !== state) return
+    sidecar.removeListener('status', listener)
+    resolve(status)
+  }
+  sidecar.on('status', listener)
+})
+
+const running = async () => {
+  sidecar.start()
+  return waitFor('running')
+}
+
+test('reports when the binary is missing', () => {
+  process.env.ZAPHNATH_SIDECAR = path.join(context.userData, 'missing')
+  assert.equal(sidecar.start().state, 'unavailable')
+  return assert.rejects(sidecar.call('ping'), /Sidecar is unavailable/)
+})
+
+test('reports a binary that cannot be started', async () => {
+  process.env.ZAPHNATH_SIDECAR = path.join(context.userData, 'not-executable')
+  fs.writeFileSync(process.env.ZAPHNATH_SIDECAR, '', { mode: 0o644 })
+  sidecar.start()
+  await waitFor('unavailable')
+  await assert.rejects(sidecar.call('ping'), /Sidecar is unavailable/)
+  assert.equal(sidecar.start().state, 'starting')
+  await sidecar.stop()
+  assert.equal(sidecar.status().state, 'stopped')
+})
+
+test('answers calls over JSON-RPC', { skip }, async () => {
+  await running()
+  assert.equal(await sidecar.call('ping'), 'pong')
+  assert.equal(await sidecar.call('countPrimes', { limit: 100 }), 25)
+  await assert.rejects(sidecar.call('nope'), { name: 'RpcError', code: -32601 })
+})
+
+test('times out and cancels slow calls', { skip }, async () => {
+  await running()
+  await assert.rejects(sidecar.call('sleep', { ms: 60000 }, { timeout: 50 }), /timed out after 50 ms/)
+
+  const controller = new AbortController()
+  const slow = sidecar.call('sleep', { ms: 60000 }, { signal: controller.signal })
+  controller.abort()
+  await assert.rejects(slow, { code: -32800 })
+  assert.equal(await sidecar.call('ping'), 'pong')
+})
+
+test('restarts after a crash and fails the calls it was running', { skip }, async () => {
+  const { pid } = await running()
+  const inFlight = sidecar.call('sleep', { ms: 60000 })
+  process.kill(pid, 'SIGKILL')
+  await assert.rejects(inFlight, /exited before responding/)
+
+  const restarted = await waitFor('running')
+  assert.notEqual(restarted.pid, pid)
+  assert.equal(restarted.restarts, 1)
+  assert.equal(await sidecar.call('ping'), 'pong')
+})
+
+test('lets a renderer cancel its own calls', { skip }, async () => {
+  await running()
+  const sender = Object.assign(new EventEmitter(), { id: 7 })
+  const other = Object.assign(new EventEmitter(), { id: 8 })
+  const slow = sidecar.callFor(sender, 'sleep', { ms: 60000 }, { requestId: 'a' })
+
+  assert.equal(sidecar.cancelFor(other, 'a'), false)
+  assert.equal(sidecar.cancelFor(sender, 'a'), true)
+  await assert.rejects(slow, { code: -32800 })
+
+  const orphaned = sidecar.callFor(sender, 'sleep', { ms: 60000 })
+  sender.emit('destroyed')
+  await assert.rejects(orphaned, { code: -32800 })
+})
+
+test('stops without restarting', { skip }, async () => {
+  await running()
+  await sidecar.stop()
+  assert.equal(sidecar.status().state, 'stopped')
+  await assert.rejects(sidecar.call('ping'), /Sidecar is stopped/)
+})
@@ -0,0 +1,115 @@
+const assert = require('assert/strict')
+const crypto = require('crypto')
+const fs = require('fs')
+const http = require('http')
+const path = require('path')
+const { afterEach, beforeEach, test } = require('node:test')
+const { setup } = require('../helpers/electron')
+
+const keys = crypto.generateKeyPairSync('ed25519')
+
+let context
+let server
+let files
+
+beforeEach(async () => {
+  context = setup()
+  files = {}
+  server = http.createServer((request, response) => {
+    const body = files[request.url]
+    if (body === undefined) {
+      response.statusCode = 404
+      return response.end()
+    }
+    response.setHeader('content-length', Buffer.byteLength(body))
+    response.end(body)
+  })
+  await new Promise((resolve) => server.listen(0, '127.0.0.1', resolve))
+  process.env.ZAPHNATH_UPDATE_FEED = `http://127.0.0.1:${server.address().port}/feed`
+  process.env.ZAPHNATH_UPDATE_KEY = path.join(context.userData, 'update-key.pem')
+  fs.writeFileSync(process.env.ZAPHNATH_UPDATE_KEY, keys.publicKey.export({ type: 'spki', format: 'pem' }))
+})
+
+afterEach(async () => {
+  delete process.env.ZAPHNATH_UPDATE_FEED
+  delete process.env.ZAPHNATH_UPDATE_KEY
+  await new Promise((resolve) => server.close(resolve))
+  context.cleanup()
+})
+
+const sign = (version, sha256) => crypto.sign(null, Buffer.from(`${version}\n${sha256}`), keys.privateKey).toString('base64')
+
+const publish = (channel, version, payload, overrides = {}) => {
+  const sha256 = crypto.createHash('sha256').update(payload).digest('hex')
+  files[`/feed/${channel}/app-${version}.bin`] = payload
+  files[`/feed/${channel}/latest-${process.platform}-${process.arch}.json`] = JSON.stringify({
+    version,
+    url: `app-${version}.bin`,
+    sha256,
+    signature: sign(version, overrides.sha256 || sha256),
+    ...overrides,
+  })
+}
+
+test('downloads, verifies and stages a newer version', async () => {
+  publish('stable', '1.1.0', 'new build')
+  const updater = context.require('updater')
+  const states = []
+  updater.on('status', ({ state }) => states.push(state))
+
+  const status = await updater.check()
+  assert.equal(status.state, 'ready')
+  assert.equal(status.version, '1.1.0')
+  assert.deepEqual([...new Set(states)], ['checking', 'downloading', 'ready'])
+  const staged = JSON.parse(fs.readFileSync(path.join(context.userData, 'updates', 'staged.json'), 'utf8'))
+  assert.equal(fs.readFileSync(staged.file, 'utf8'), 'new build')
+})
+
+test('reports up-to-date when the feed is not newer', async () => {
+  publish('stable', '1.0.0', 'same build')
+  const updater = context.require('updater')
+  assert.equal((await updater.check()).state, 'up-to-date')
+})
+
+test('ignores prereleases on the stable channel but not on beta', async () => {
+  publish('stable', '1.1.0-beta.1', 'beta build')
+  publish('beta', '1.1.0-beta.1', 'beta build')
+  const updater = context.require('updater')
+  assert.equal((await updater.check()).state, 'up-to-date')
+  context.require('settings').set('updates.channel', 'beta')
+  assert.equal((await updater.check()).state, 'ready')
+})
+
+test('rejects downloads whose checksum does not match', async () => {
+  publish('stable', '1.1.0', 'tampered', { sha256: '0'.repeat(64) })
+  const updater = context.require('updater')
+  const status = await updater.check()
+  assert.equal(status.state, 'error')
+  assert.match(status.message, /Checksum mismatch/)
+  assert.equal(fs.existsSync(path.join(context.userData, 'updates', 'staged.json')), false)
+})
+
+test('is disabled without a feed', async () => {
+  delete process.env.ZAPHNATH_UPDATE_FEED
+  const updater = context.require('updater')
+  assert.equal((await updater.check()).state, 'disabled')
+})
+
+test('refuses unsigned or badly signed manifests', async () => {
+  const updater = context.require('updater')
+  publish('stable', '1.1.0', 'new build', { signature: undefined })
+  assert.match((await updater.check()).message, /not signed/)
+  publish('stable', '1.1.0', 'new build', { signature: sign('1.2.0', '0'.repeat(64)) })
+  assert.match((await updater.check()).message, /signature is invalid/)
+  assert.equal(fs.existsSync(path.join(context.userData, 'updates', 'staged.json')), false)
+})
+
+test('refuses to update when no signing key is bundled', async () => {
+  fs.rmSync(process.env.ZAPHNATH_UPDATE_KEY)
+  publish('stable', '1.1.0', 'new build')
+  const updater = context.require('updater')
+  const status = await updater.check()
+  assert.equal(status.state, 'error')
+  assert.match(status.message, /No update signing key/)
+  assert.equal(fs.existsSync(path.join(context.userData, 'updates', 'staged.json')), false)
+})
@@ -0,0 +1,41 @@
+const assert = require('assert/strict')
+const fs = require('fs')
+const path = require('path')
+const { afterEach, beforeEach, test } = require('node:test')
+const { setup } = require('../helpers/electron')
+
+let context
+
+beforeEach(() => {
+  context = setup()
+})
+
+afterEach(() => context.cleanup())
+
+const save = (state) => {
+  fs.writeFileSync(path.join(context.userData, 'window-state.json'), JSON.stringify(state))
+}
+
+test('uses the defaults when nothing is saved', () => {
+  const windowState = context.require('window-state')
+  assert.deepEqual(windowState.restore('main', { width: 800, height: 600 }), { width: 800, height: 600 })
+})
+
+test('restores saved bounds on a display that still exists', () => {
+  save({ main: { bounds: { x: 100, y: 50, width: 900, height: 700 }, displayId: 1 } })
+  const windowState = context.require('window-state')
+  assert.deepEqual(windowState.restore('main', { width: 800, height: 600 }), { x: 100, y: 50, width: 900, height: 700 })
+})
+
+test('re-centers when the saved display is gone', () => {
+  save({ main: { bounds: { x: 2500, y: 50, width: 3000, height: 700 }, displayId: 2 } })
+  const windowState = context.require('window-state')
+  assert.deepEqual(windowState.restore('main', { width: 800, height: 600 }), { width: 1920, height: 700, center: true })
+})
+
+test('ignores saved state when restoring is turned off', () => {
+  save({ main: { bounds: { x: 100, y: 50, width: 900, height: 700 }, displayId: 1 } })
+  context.require('settings').set('window.restoreState', false)
+  const windowState = context.require('window-state')
+  assert.deepEqual(windowState.restore('main', { width: 800, height: 600 }), { width: 800, height: 600 })
+})
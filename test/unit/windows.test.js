@@ -0,0 +1,61 @@
+const assert = require('assert/strict')
+const { EventEmitter } = require('events')
+const fs = require('fs')
+const path = require('path')
+const { afterEach, beforeEach, test } = require('node:test')
+const { setup } = require('../helpers/electron')
+
+let context
+let created
+
+class FakeWindow extends EventEmitter {
+  constructor(options) {
+    super()
+    this.id = created.length + 1
+    this.options = options
+    this.webContents = Object.assign(new EventEmitter(), { id: this.id })
+    created.push(this)
+  }
+
+  getBounds() {
+    const { x = 100, y = 100, width, height } = this.options
+    return { x, y, width, height }
+  }
+
+  isDestroyed() {
+    return false
+  }
+
+  loadURL() {}
+}
+
+beforeEach(() => {
+  created = []
+  context = setup({ BrowserWindow: FakeWindow })
+})
+
+afterEach(() => context.cleanup())
+
+test('cascades document windows instead of stacking them', () => {
+  const windows = context.require('windows')
+  windows.open('document')
+  windows.open('document')
+  windows.open('document')
+  assert.deepEqual(created.map(({ options: { x, y } }) => [x, y]), [[undefined, undefined], [124, 124], [148, 148]])
+})
+
+test('wraps the cascade at the edge of the work area', () => {
+  const windows = context.require('windows')
+  windows.open('document')
+  created[0].options.x = 1900
+  windows.open('document')
+  assert.deepEqual([created[1].options.x, created[1].options.y], [0, 0])
+})
+
+test('does not restore saved state for document windows', () => {
+  fs.writeFileSync(path.join(context.userData, 'window-state.json'), JSON.stringify({
+    document: { bounds: { x: 300, y: 200, width: 900, height: 700 }, displayId: 1 },
+  }))
+  context.require('windows').open('document')
+  assert.deepEqual([created[0].options.x, created[0].options.width], [undefined, 800])
+})